@@ -1,5 +1,7 @@
 //! Solutions to 2022 day 5 problems
 //!
+use std::fmt;
+
 use crate::read_file;
 
 type Towers = Vec<Vec<char>>;
@@ -8,6 +10,30 @@ type Towers = Vec<Vec<char>>;
 /// count, from, to
 struct Instruction(usize, usize, usize);
 
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.0, self.1 + 1, self.2 + 1)
+    }
+}
+
+/// Crane model, which determines how multiple crates are moved
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Crane {
+    /// moves crates one at a time, reversing their order
+    CrateMover9000,
+    /// moves crates all at once, preserving their order
+    CrateMover9001,
+}
+
+impl Crane {
+    fn apply(&self, towers: Towers, instruction: &Instruction) -> Towers {
+        match self {
+            Crane::CrateMover9000 => process(towers, *instruction),
+            Crane::CrateMover9001 => process_two(towers, instruction),
+        }
+    }
+}
+
 fn parse_towers(input: &str) -> Towers {
     let iter = input.lines().rev();
     let tower_count: usize = iter
@@ -37,6 +63,33 @@ fn parse_towers(input: &str) -> Towers {
     res
 }
 
+/// returns the towers drawn in the same bracketed format as the puzzle input
+fn render(towers: &Towers) -> String {
+    let height = towers.iter().map(Vec::len).max().unwrap_or(0);
+    let mut result: Vec<String> = (0..height)
+        .rev()
+        .map(|row| {
+            towers
+                .iter()
+                .map(|tower| match tower.get(row) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    let labels = (1..=towers.len())
+        .map(|idx| format!(" {} ", idx))
+        .collect::<Vec<_>>()
+        .join(" ");
+    result.push(labels.trim_end().to_string());
+
+    result.join("\n")
+}
+
 fn parse_instructions(input: &str) -> Vec<Instruction> {
     input
         .lines()
@@ -112,6 +165,31 @@ pub fn two(file_path: &str) -> String {
         .into()
 }
 
+/// returns the initial towers followed by a drawing of the towers after every `every`th
+/// instruction, and after the final one
+pub fn trace(file_path: &str, crane: Crane, every: usize) -> Result<String, String> {
+    if every == 0 {
+        return Err("Trace interval must be at least 1".to_string());
+    }
+    let input = read_file(file_path);
+    let (tower_str, instruction_str) = input
+        .split_once("\n\n")
+        .expect("Failed to parse input file.");
+    let instructions = parse_instructions(instruction_str);
+    let mut towers = parse_towers(tower_str);
+    let mut result = vec![format!("Initial\n{}", render(&towers))];
+
+    for (idx, instruction) in instructions.iter().enumerate() {
+        towers = crane.apply(towers, instruction);
+        let count = idx + 1;
+        if count % every == 0 || count == instructions.len() {
+            result.push(format!("{}: {}\n{}", count, instruction, render(&towers)));
+        }
+    }
+
+    Ok(result.join("\n\n"))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -131,4 +209,86 @@ mod test {
         let actual = two("input/05-t.txt");
         assert_eq!(actual, expected, "{}", msg);
     }
+
+    #[test]
+    fn rendering() {
+        let msg = "should draw the towers in the input format";
+        let input = read_file("input/05-t.txt");
+        let (tower_str, _) = input.split_once("\n\n").unwrap();
+        let expected = tower_str
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let actual = render(&parse_towers(tower_str));
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn tracing() {
+        let msg = "should draw the towers after every instruction";
+        let actual = trace("input/05-t.txt", Crane::CrateMover9000, 1).unwrap();
+        let expected = "\
+Initial
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+1: move 1 from 2 to 1
+[D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+2: move 3 from 1 to 3
+        [Z]
+        [N]
+    [C] [D]
+    [M] [P]
+ 1   2   3
+
+3: move 2 from 2 to 1
+        [Z]
+        [N]
+[M]     [D]
+[C]     [P]
+ 1   2   3
+
+4: move 1 from 1 to 2
+        [Z]
+        [N]
+        [D]
+[C] [M] [P]
+ 1   2   3";
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should draw the towers after every third instruction, and the last";
+        let actual = trace("input/05-t.txt", Crane::CrateMover9001, 3).unwrap();
+        let expected = "\
+Initial
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+3: move 2 from 2 to 1
+        [D]
+        [N]
+[C]     [Z]
+[M]     [P]
+ 1   2   3
+
+4: move 1 from 1 to 2
+        [D]
+        [N]
+        [Z]
+[M] [C] [P]
+ 1   2   3";
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should return an error for an interval of 0";
+        let actual = trace("input/05-t.txt", Crane::CrateMover9000, 0);
+        assert!(actual.is_err(), "{}", msg);
+    }
 }
@@ -1,37 +1,19 @@
 //! Solutions to 2020 day 9 problems
 //! --- Day 9: Rope Bridge ---
 use std::collections::HashSet;
-use std::ops::{Add, Sub};
 
 use parser::three::lib::{choice, keep_first, p_char, p_int};
 
-use crate::read_file;
-
-/// Grid direction
-#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq)]
-pub struct Direction(pub isize, pub isize);
-
-impl Add for Direction {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self(self.0 + other.0, self.1 + other.1)
-    }
-}
-
-impl Sub for Direction {
-    type Output = Self;
-
-    fn sub(self, other: Self) -> Self {
-        Self(self.0 - other.0, self.1 - other.1)
-    }
-}
+use crate::{
+    geometry::{Direction, EAST, NORTH, SOUTH, WEST},
+    read_file,
+};
 
 fn parse_line(input: &str) -> Result<(Direction, isize), String> {
-    let w = p_char('L').map(|_| Direction(-1, 0));
-    let e = p_char('R').map(|_| Direction(1, 0));
-    let n = p_char('U').map(|_| Direction(0, 1));
-    let s = p_char('D').map(|_| Direction(0, -1));
+    let w = p_char('L').map(|_| WEST);
+    let e = p_char('R').map(|_| EAST);
+    let n = p_char('U').map(|_| NORTH);
+    let s = p_char('D').map(|_| SOUTH);
     let all = choice([n, e, s, w]);
 
     let space = p_char(' ');
@@ -1,93 +1,253 @@
 //! Solutions to 2020 day 10 problems
 //! --- Day 10: Cathode-Ray Tube ---
-use parser::three::lib::{choice, keep_first, p_char, p_int, p_string};
+use std::fmt::{self, Write};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use parser::three::lib::{any_of, choice, keep_first, p_char, p_int, p_string};
 
 use crate::read_file;
 
-fn parse_line(input: &str) -> Result<Option<isize>, String> {
-    let space = p_char(' ');
-    let digit = p_int(10);
+/// CPU register
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Register {
+    /// general purpose
+    W,
+    /// sprite position, the only register used by the handheld's programs
+    X,
+    /// general purpose
+    Y,
+    /// general purpose
+    Z,
+}
+
+impl From<char> for Register {
+    fn from(character: char) -> Self {
+        match character {
+            'w' => Self::W,
+            'x' => Self::X,
+            'y' => Self::Y,
+            'z' => Self::Z,
+            _ => panic!("Invalid register: '{}'", character),
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::W => 'w',
+            Self::X => 'x',
+            Self::Y => 'y',
+            Self::Z => 'z',
+        };
 
-    let addx = p_string("addx");
-    let op_addx = keep_first(addx, space)
-        .and_then(digit)
-        .map(|(_, value)| Some(value));
-    let noop = p_string("noop").map(|_| None);
+        write!(f, "{}", name)
+    }
+}
 
-    let parser = choice([op_addx, noop]).with_label("CPU instruction".to_string());
+/// Values of every [`Register`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Registers([isize; 4]);
 
-    match parser.parse(input) {
-        Ok((_input, value)) => Ok(value),
-        Err(err) => Err(format!("{}", err)),
+impl Default for Registers {
+    /// every register starts with the value 1
+    fn default() -> Self {
+        Self([1; 4])
     }
 }
 
-fn exec(
-    (mut result, mut cycle, mut reg_x): ([isize; 240], usize, isize),
-    op: &Option<isize>,
-) -> ([isize; 240], usize, isize) {
-    match op {
-        Some(value) => {
-            for _ in 0..2 {
-                if cycle < result.len() {
-                    result[cycle - 1] = reg_x;
-                }
-                cycle += 1;
-            }
-            reg_x += value;
+impl Index<Register> for Registers {
+    type Output = isize;
+
+    fn index(&self, register: Register) -> &Self::Output {
+        &self.0[register as usize]
+    }
+}
+
+impl IndexMut<Register> for Registers {
+    fn index_mut(&mut self, register: Register) -> &mut Self::Output {
+        &mut self.0[register as usize]
+    }
+}
+
+/// CPU instruction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    /// does nothing
+    Noop,
+    /// adds the value to the register
+    Add(Register, isize),
+}
+
+impl Instruction {
+    /// number of cycles the instruction takes to complete
+    pub fn cycles(&self) -> usize {
+        match self {
+            Self::Noop => 1,
+            Self::Add(..) => 2,
         }
-        None => {
-            if cycle < result.len() {
-                result[cycle - 1] = reg_x;
-            }
-            cycle += 1;
+    }
+
+    /// applies the effects of the instruction once it completes
+    fn apply(&self, registers: &mut Registers) {
+        match *self {
+            Self::Noop => (),
+            Self::Add(register, value) => registers[register] += value,
         }
-    };
+    }
+}
 
-    (result, cycle, reg_x)
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Noop => write!(f, "noop"),
+            Self::Add(register, value) => write!(f, "add{} {}", register, value),
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let space = p_char(' ');
+        let digit = p_int(10);
+        let register = any_of(['w', 'x', 'y', 'z']).map(Register::from);
+
+        let add = keep_first(p_string("add").and_then(register), space)
+            .and_then(digit)
+            .map(|((_, register), value)| Instruction::Add(register, value));
+        let noop = p_string("noop").map(|_| Instruction::Noop);
+
+        let parser = choice([add, noop]).with_label("CPU instruction".to_string());
+
+        match parser.parse(input) {
+            Ok((_input, value)) => Ok(value),
+            Err(err) => Err(format!("{}", err)),
+        }
+    }
+}
+
+fn parse_program(input: &str) -> Result<Vec<Instruction>, String> {
+    input.lines().map(str::parse).collect()
+}
+
+/// CPU state during a single cycle
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    /// cycle number, counting from 1
+    pub number: usize,
+    /// index of the instruction being executed
+    pub pc: usize,
+    /// register values during the cycle
+    pub registers: Registers,
+}
+
+impl Cycle {
+    /// cycle number multiplied by the value of the X register
+    pub fn signal_strength(&self) -> isize {
+        self.number as isize * self.registers[Register::X]
+    }
+}
+
+/// Handheld CPU, running a program one cycle at a time.
+///
+/// Iterating yields the state during each cycle until the program completes.
+#[derive(Clone, Debug)]
+pub struct Cpu<'a> {
+    program: &'a [Instruction],
+    /// index of the current instruction
+    pc: usize,
+    /// cycles already spent on the current instruction
+    elapsed: usize,
+    /// number of cycles completed
+    cycle: usize,
+    registers: Registers,
+}
+
+impl<'a> Cpu<'a> {
+    /// Create a CPU that will run `program` from the start
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Self {
+            program,
+            pc: 0,
+            elapsed: 0,
+            cycle: 0,
+            registers: Default::default(),
+        }
+    }
+
+    /// Returns the current register values
+    pub fn registers(&self) -> Registers {
+        self.registers
+    }
+
+    /// Returns true once every instruction has completed
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+}
+
+impl<'a> Iterator for Cpu<'a> {
+    type Item = Cycle;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = self.program.get(self.pc)?;
+        self.cycle += 1;
+        let during = Cycle {
+            number: self.cycle,
+            pc: self.pc,
+            registers: self.registers,
+        };
+
+        self.elapsed += 1;
+        if self.elapsed == instruction.cycles() {
+            instruction.apply(&mut self.registers);
+            self.pc += 1;
+            self.elapsed = 0;
+        }
+
+        Some(during)
+    }
 }
 
 /// returns the sum of the signal strength during the 20th, 60th, 100th, 140th,
 /// 180th, and 220th cycles
 pub fn one(file_path: &str) -> isize {
-    let ops: Vec<_> = read_file(file_path)
-        .lines()
-        .map(parse_line)
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-    let result = ops.iter().fold(([0; 240], 1, 1), exec).0;
+    let program = parse_program(&read_file(file_path)).unwrap();
 
-    result
-        .iter()
-        .enumerate()
-        .skip(19)
-        .step_by(40)
-        .map(|(idx, value)| (idx + 1) as isize * value)
+    Cpu::new(&program)
+        .take(220)
+        .filter(|cycle| cycle.number % 40 == 20)
+        .map(|cycle| cycle.signal_strength())
         .sum()
 }
 
-/// returns the rendered screen
-pub fn two(file_path: &str) -> String {
-    let ops: Vec<_> = read_file(file_path)
-        .lines()
-        .map(parse_line)
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-    let result = ops.iter().fold(([0; 240], 1, 1), exec).0;
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
 
-    const WIDTH: usize = 40;
+/// returns the CRT pixel drawn during `cycle`: lit if the 3 pixel wide sprite centered on X
+/// covers the position being drawn
+fn pixel(cycle: &Cycle) -> char {
+    let col = ((cycle.number - 1) % WIDTH) as isize;
+    let sprite = cycle.registers[Register::X];
+    if (sprite - 1..=sprite + 1).contains(&col) {
+        '#'
+    } else {
+        '.'
+    }
+}
 
-    result
-        .iter()
-        .enumerate()
-        .map(|(idx, &value)| {
-            let col = (idx % WIDTH) as isize;
-            if (value - 1..=value + 1).contains(&col) {
-                '#'
-            } else {
-                '.'
-            }
-        })
+/// returns the screen drawn over the cycles of `trace`
+fn draw<I>(trace: I) -> String
+where
+    I: IntoIterator<Item = Cycle>,
+{
+    trace
+        .into_iter()
+        .take(WIDTH * HEIGHT)
+        .map(|cycle| pixel(&cycle))
         .collect::<Vec<_>>()
         .chunks(WIDTH)
         .map(|line| line.iter().collect())
@@ -95,10 +255,269 @@ pub fn two(file_path: &str) -> String {
         .join("\n")
 }
 
+/// letters of the handheld's font, each drawn in a 5 column cell
+const FONT: [(char, [&str; HEIGHT]); 18] = [
+    ('A', [".##..", "#..#.", "#..#.", "####.", "#..#.", "#..#."]),
+    ('B', ["###..", "#..#.", "###..", "#..#.", "#..#.", "###.."]),
+    ('C', [".##..", "#..#.", "#....", "#....", "#..#.", ".##.."]),
+    ('E', ["####.", "#....", "###..", "#....", "#....", "####."]),
+    ('F', ["####.", "#....", "###..", "#....", "#....", "#...."]),
+    ('G', [".##..", "#..#.", "#....", "#.##.", "#..#.", ".###."]),
+    ('H', ["#..#.", "#..#.", "####.", "#..#.", "#..#.", "#..#."]),
+    ('I', [".###.", "..#..", "..#..", "..#..", "..#..", ".###."]),
+    ('J', ["..##.", "...#.", "...#.", "...#.", "#..#.", ".##.."]),
+    ('K', ["#..#.", "#.#..", "##...", "#.#..", "#.#..", "#..#."]),
+    ('L', ["#....", "#....", "#....", "#....", "#....", "####."]),
+    ('O', [".##..", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+    ('P', ["###..", "#..#.", "#..#.", "###..", "#....", "#...."]),
+    ('R', ["###..", "#..#.", "#..#.", "###..", "#.#..", "#..#."]),
+    ('S', [".###.", "#....", "#....", ".##..", "...#.", "###.."]),
+    ('U', ["#..#.", "#..#.", "#..#.", "#..#.", "#..#.", ".##.."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####.", "...#.", "..#..", ".#...", "#....", "####."]),
+];
+
+/// cell width of each letter, including the blank column separating it from the next
+const LETTER_WIDTH: usize = 5;
+
+/// reads the letters drawn on a screen of `#` and `.` pixels
+pub fn ocr(screen: &str) -> Result<String, String> {
+    let rows: Vec<Vec<char>> = screen.lines().map(|line| line.chars().collect()).collect();
+    if rows.len() != HEIGHT {
+        return Err(format!("Expected {} rows, found {}", HEIGHT, rows.len()));
+    }
+    let width = rows[0].len();
+    if let Some(row) = rows.iter().position(|row| row.len() != width) {
+        return Err(format!("Row {} is not {} pixels wide", row, width));
+    }
+
+    (0..width)
+        .step_by(LETTER_WIDTH)
+        .map(|col| {
+            // the final cell may be missing its blank column
+            let cell: Vec<String> = rows
+                .iter()
+                .map(|row| {
+                    (col..col + LETTER_WIDTH)
+                        .map(|idx| row.get(idx).copied().unwrap_or('.'))
+                        .collect()
+                })
+                .collect();
+
+            FONT.iter()
+                .find(|(_, glyph)| glyph.iter().zip(&cell).all(|(a, b)| a == b))
+                .map(|&(letter, _)| letter)
+                .ok_or_else(|| format!("Unknown glyph at column {}:\n{}", col, cell.join("\n")))
+        })
+        .collect()
+}
+
+/// The CRT screen, as drawn and as read
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Screen {
+    /// rows of `#` and `.` pixels
+    pub raster: String,
+    /// letters on the screen, or the reason they could not be read
+    pub text: Result<String, String>,
+}
+
+// show the letters when they can be read, and the pixels otherwise
+impl fmt::Display for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.text {
+            Ok(text) => write!(f, "{}", text),
+            Err(_) => write!(f, "{}", self.raster),
+        }
+    }
+}
+
+/// returns the rendered screen, and the letters drawn on it
+pub fn two(file_path: &str) -> Screen {
+    let program = parse_program(&read_file(file_path)).unwrap();
+    let raster = draw(Cpu::new(&program));
+    let text = ocr(&raster);
+
+    Screen { raster, text }
+}
+
+/// Condition that pauses a [`Debugger`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Breakpoint {
+    /// pause after the cycle with this number
+    Cycle(usize),
+    /// pause after the first cycle of the instruction at this index
+    Instruction(usize),
+}
+
+/// Step debugger for the handheld CPU, recording the screen as it is drawn
+#[derive(Clone, Debug)]
+pub struct Debugger<'a> {
+    cpu: Cpu<'a>,
+    program: &'a [Instruction],
+    breakpoints: Vec<Breakpoint>,
+    /// pixels drawn so far
+    pixels: Vec<char>,
+    /// most recently executed cycle
+    last: Option<Cycle>,
+}
+
+impl<'a> Debugger<'a> {
+    /// Create a debugger that will run `program` from the start
+    pub fn new(program: &'a [Instruction]) -> Self {
+        Self {
+            cpu: Cpu::new(program),
+            program,
+            breakpoints: Vec::new(),
+            pixels: Vec::with_capacity(WIDTH * HEIGHT),
+            last: None,
+        }
+    }
+
+    /// Pause whenever `breakpoint` is reached
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        if !self.breakpoints.contains(&breakpoint) {
+            self.breakpoints.push(breakpoint);
+        }
+    }
+
+    /// Stop pausing at `breakpoint`, returning false if it was not set
+    pub fn remove_breakpoint(&mut self, breakpoint: Breakpoint) -> bool {
+        let count = self.breakpoints.len();
+        self.breakpoints.retain(|&existing| existing != breakpoint);
+
+        self.breakpoints.len() != count
+    }
+
+    /// Runs a single cycle, returning its state, or None once the program has completed
+    pub fn step(&mut self) -> Option<Cycle> {
+        let cycle = self.cpu.next()?;
+        if self.pixels.len() < WIDTH * HEIGHT {
+            self.pixels.push(pixel(&cycle));
+        }
+        self.last = Some(cycle);
+
+        Some(cycle)
+    }
+
+    fn is_break(&self, cycle: &Cycle, previous: Option<Cycle>) -> bool {
+        let is_first = !matches!(previous, Some(previous) if previous.pc == cycle.pc);
+
+        self.breakpoints.iter().any(|&breakpoint| match breakpoint {
+            Breakpoint::Cycle(number) => cycle.number == number,
+            Breakpoint::Instruction(idx) => is_first && cycle.pc == idx,
+        })
+    }
+
+    /// Runs until a breakpoint is reached, returning the cycle that reached it, or None once the
+    /// program has completed
+    pub fn resume(&mut self) -> Option<Cycle> {
+        loop {
+            let previous = self.last;
+            let cycle = self.step()?;
+            if self.is_break(&cycle, previous) {
+                return Some(cycle);
+            }
+        }
+    }
+
+    /// Returns one line describing `cycle`: its number, the instruction executing and the
+    /// register values
+    pub fn dump(&self, cycle: &Cycle) -> String {
+        let instruction = self
+            .program
+            .get(cycle.pc)
+            .map_or_else(|| "-".to_string(), ToString::to_string);
+        let registers = [Register::W, Register::X, Register::Y, Register::Z]
+            .iter()
+            .map(|&register| format!("{} {:>3}", register, cycle.registers[register]))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        format!(
+            "cycle {:>3}  pc {:>3}  {:<10}  {}",
+            cycle.number, cycle.pc, instruction, registers
+        )
+    }
+
+    /// Returns the screen drawn so far. The row being drawn is only as long as the pixels
+    /// already drawn on it.
+    pub fn screen(&self) -> String {
+        self.pixels
+            .chunks(WIDTH)
+            .map(|line| line.iter().collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// runs the program at `file_path` under the debugger, returning a register dump for every cycle
+/// and the screen as drawn so far at each of the `breakpoints`
+pub fn debug(file_path: &str, breakpoints: &[Breakpoint]) -> String {
+    let program = parse_program(&read_file(file_path)).unwrap();
+    let mut debugger = Debugger::new(&program);
+    for &breakpoint in breakpoints {
+        debugger.add_breakpoint(breakpoint);
+    }
+
+    let mut result = String::new();
+    loop {
+        let previous = debugger.last;
+        let cycle = match debugger.step() {
+            Some(cycle) => cycle,
+            None => break,
+        };
+        writeln!(result, "{}", debugger.dump(&cycle)).unwrap();
+        if debugger.is_break(&cycle, previous) {
+            writeln!(result, "-- break --\n{}\n-----------", debugger.screen()).unwrap();
+        }
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn parse() {
+        let msg = "should parse a CPU instruction";
+        let cases = [
+            ("noop", Ok(Instruction::Noop)),
+            ("addx 3", Ok(Instruction::Add(Register::X, 3))),
+            ("addy -11", Ok(Instruction::Add(Register::Y, -11))),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse(), expected, "{}", msg);
+        }
+        assert!("addq 3".parse::<Instruction>().is_err(), "{}", msg);
+    }
+
+    #[test]
+    fn trace() {
+        let msg = "should return the register values during each cycle";
+        let program = parse_program("noop\naddx 3\naddx -5").unwrap();
+        let actual: Vec<_> = Cpu::new(&program)
+            .map(|cycle| (cycle.number, cycle.pc, cycle.registers[Register::X]))
+            .collect();
+        let expected = vec![(1, 0, 1), (2, 1, 1), (3, 1, 1), (4, 2, 4), (5, 2, 4)];
+        assert_eq!(actual, expected, "{}", msg);
+
+        let mut cpu = Cpu::new(&program);
+        while cpu.next().is_some() {}
+        assert!(cpu.is_halted(), "{}", msg);
+        assert_eq!(cpu.registers()[Register::X], -1, "{}", msg);
+        assert_eq!(cpu.registers()[Register::W], 1, "{}", msg);
+    }
+
+    #[test]
+    fn unbounded() {
+        let msg = "should keep running past the 240 cycles of the screen";
+        let program = vec![Instruction::Add(Register::W, 1); 200];
+        let actual = Cpu::new(&program).last().map(|cycle| cycle.number);
+        assert_eq!(actual, Some(400), "{}", msg);
+    }
+
     #[test]
     fn part_one() {
         let msg = "should return the sum of the signal strength during the 20th, 60th, 100th, 140th, 180th, and 220th cycles";
@@ -116,7 +535,103 @@ mod test {
 #####.....#####.....#####.....#####.....
 ######......######......######......####
 #######.......#######.......#######.....";
-        let actual = two("input/10-t.txt");
+        let actual = two("input/10-t.txt").raster;
         assert_eq!(actual, expected, "{}", msg);
     }
+
+    #[test]
+    fn breakpoints() {
+        let msg = "should pause at each breakpoint";
+        let program = parse_program("noop\naddx 3\naddx -5\nnoop").unwrap();
+        let mut debugger = Debugger::new(&program);
+        debugger.add_breakpoint(Breakpoint::Cycle(3));
+        debugger.add_breakpoint(Breakpoint::Instruction(2));
+        debugger.add_breakpoint(Breakpoint::Instruction(3));
+
+        let actual: Vec<_> = std::iter::from_fn(|| debugger.resume())
+            .map(|cycle| cycle.number)
+            .collect();
+        assert_eq!(actual, vec![3, 4, 6], "{}", msg);
+
+        let msg = "should stop pausing at a removed breakpoint";
+        let mut debugger = Debugger::new(&program);
+        debugger.add_breakpoint(Breakpoint::Cycle(3));
+        assert!(debugger.remove_breakpoint(Breakpoint::Cycle(3)), "{}", msg);
+        assert!(!debugger.remove_breakpoint(Breakpoint::Cycle(3)), "{}", msg);
+        assert_eq!(debugger.resume(), None, "{}", msg);
+    }
+
+    #[test]
+    fn single_step() {
+        let msg = "should run a single cycle";
+        let program = parse_program("noop\naddx 3\naddx -5").unwrap();
+        let mut debugger = Debugger::new(&program);
+        debugger.step();
+        debugger.step();
+        let cycle = debugger.step().unwrap();
+        let expected = "cycle   3  pc   1  addx 3      w   1  x   1  y   1  z   1";
+        assert_eq!(debugger.dump(&cycle), expected, "{}", msg);
+        assert_eq!(debugger.screen(), "###", "{}", msg);
+    }
+
+    #[test]
+    fn debug_session() {
+        let msg = "should log every cycle and show the screen at each breakpoint";
+        let actual = debug("input/10-t.txt", &[Breakpoint::Cycle(41)]);
+        let lines: Vec<_> = actual.lines().collect();
+        assert_eq!(lines.len(), 240 + 4, "{}", msg);
+        assert!(
+            lines[0].starts_with("cycle   1  pc   0  addx 15"),
+            "{}",
+            msg
+        );
+        let expected = [
+            "-- break --",
+            "##..##..##..##..##..##..##..##..##..##..",
+            "#",
+            "-----------",
+        ];
+        assert_eq!(lines[41..45], expected, "{}", msg);
+    }
+
+    /// every known letter, drawn independently of [`FONT`]
+    const ALPHABET: [&str; HEIGHT] = [
+        ".##..###...##..####.####..##..#..#..###...##.#..#.#.....##..###..###...###.#..#.#...#####.",
+        "#..#.#..#.#..#.#....#....#..#.#..#...#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#...#.",
+        "#..#.###..#....###..###..#....####...#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#...#..",
+        "####.#..#.#....#....#....#.##.#..#...#.....#.#.#..#....#..#.###..###...##..#..#...#...#...",
+        "#..#.#..#.#..#.#....#....#..#.#..#...#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#..#....",
+        "#..#.###...##..####.#.....###.#..#..###..##..#..#.####..##..#....#..#.###...##....#..####.",
+    ];
+
+    #[test]
+    fn read_letters() {
+        let msg = "should read every known letter";
+        let actual = ocr(&ALPHABET.join("\n"));
+        assert_eq!(actual, Ok("ABCEFGHIJKLOPRSUYZ".to_string()), "{}", msg);
+
+        let msg = "should read a screen without the final blank column";
+        let screen: Vec<_> = ALPHABET
+            .iter()
+            .map(|line| &line[..line.len() - 1])
+            .collect();
+        let actual = ocr(&screen.join("\n"));
+        assert_eq!(actual, Ok("ABCEFGHIJKLOPRSUYZ".to_string()), "{}", msg);
+
+        let msg = "should read the letters drawn by a real program";
+        let actual = two("input/10-1.txt").text;
+        assert_eq!(actual, Ok("RFKZCPEF".to_string()), "{}", msg);
+    }
+
+    #[test]
+    fn unknown_glyph() {
+        let msg = "should return an error for an unknown glyph";
+        let screen = ["###..", "#..#.", "#..#.", "####.", "#..#.", "#..#."];
+        assert!(ocr(&screen.join("\n")).is_err(), "{}", msg);
+        let actual = two("input/10-t.txt").text;
+        assert!(actual.is_err(), "{}", msg);
+
+        let msg = "should return an error for a screen of the wrong size";
+        assert!(ocr("#..#.\n#..#.").is_err(), "{}", msg);
+    }
 }
@@ -1,34 +1,95 @@
 //! Solutions to 2020 day 11 problems
 //! --- Day 11: Monkey in the Middle ---
-use std::ops::{Add, Mul};
+use std::fmt;
 use std::str::FromStr;
 
 use parser::three::lib::{
-    between, choice, digit_char, keep_second, one_or_more, p_char, p_int, p_string, sep_by, spaces,
+    between, choice, digit_char, keep_second, one_or_more, p_char, p_string, sep_by, spaces,
 };
 
 use crate::read_file;
 
-type Op = Box<dyn Fn(usize) -> usize>;
+/// Right hand side of an [`Operation`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operand {
+    /// the current worry level
+    Old,
+    /// a constant
+    Value(usize),
+}
 
-fn add(op_value: usize) -> Op {
-    Box::new(move |i| op_value.add(i))
+impl Operand {
+    fn value(&self, old: usize) -> usize {
+        match self {
+            Self::Old => old,
+            Self::Value(value) => *value,
+        }
+    }
 }
 
-fn multiply(op_value: usize) -> Op {
-    Box::new(move |i| op_value.mul(i))
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Old => write!(f, "old"),
+            Self::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// How a monkey changes the worry level of an item it inspects
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    /// new = old + operand
+    Add(Operand),
+    /// new = old * operand
+    Multiply(Operand),
+}
+
+impl Operation {
+    /// returns the new worry level
+    pub fn apply(&self, old: usize) -> usize {
+        match self {
+            Self::Add(operand) => old + operand.value(old),
+            Self::Multiply(operand) => old * operand.value(old),
+        }
+    }
 }
 
-struct Monkey {
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Add(operand) => write!(f, "old + {}", operand),
+            Self::Multiply(operand) => write!(f, "old * {}", operand),
+        }
+    }
+}
+
+/// A monkey playing keep away
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Monkey {
+    /// position in the list of monkeys
+    pub id: usize,
+    /// worry levels of the held items
     pub items: Vec<usize>,
-    operation: Op,
-    test: usize,
-    target: (usize, usize),
+    /// change in worry level when an item is inspected
+    pub operation: Operation,
+    /// divisor deciding which monkey an item is thrown to
+    pub test: usize,
+    /// monkeys to throw to when the test passes and fails
+    pub target: (usize, usize),
 }
 
 impl Monkey {
-    pub fn new(items: Vec<usize>, operation: Op, test: usize, target: (usize, usize)) -> Self {
+    /// Create a monkey
+    pub fn new(
+        id: usize,
+        items: Vec<usize>,
+        operation: Operation,
+        test: usize,
+        target: (usize, usize),
+    ) -> Self {
         Self {
+            id,
             items,
             operation,
             test,
@@ -37,71 +98,231 @@ impl Monkey {
     }
 }
 
-impl FromStr for Monkey {
-    type Err = String;
+// matches the puzzle input format
+impl fmt::Display for Monkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = self
+            .items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
 
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let digit = p_int(10).map(|i| i as usize);
-        let digits = one_or_more(digit_char(10)).map::<String>(|s| s.into_iter().collect());
-        let comma = p_char(',').and_then(spaces());
-
-        let spaced = |s| between(spaces(), p_string(s), spaces());
-
-        let int_list = sep_by(digit.clone(), comma.clone());
-        let spaced_int = between(spaces(), digit.clone(), spaces());
-        let mult = p_char('*').map::<fn(_) -> _>(|_| multiply);
-        let plus = p_char('+').map::<fn(_) -> _>(|_| add);
-        let ops = choice::<fn(_) -> _>([plus, mult])
-            .and_then(between(
-                spaces(),
-                choice([p_string("old"), digits.clone()]),
-                spaces(),
-            ))
-            .map(|(op, value)| {
-                if let Ok(value) = value.parse::<_>() {
-                    // if value is number
-                    op(value)
-                } else {
-                    // otherwise it's "old"
-                    Box::new(move |i| op(i)(i))
-                }
-            });
+        writeln!(f, "Monkey {}:", self.id)?;
+        writeln!(f, "  Starting items: {}", items)?;
+        writeln!(f, "  Operation: new = {}", self.operation)?;
+        writeln!(f, "  Test: divisible by {}", self.test)?;
+        writeln!(f, "    If true: throw to monkey {}", self.target.0)?;
+        write!(f, "    If false: throw to monkey {}", self.target.1)
+    }
+}
 
-        let p_items = keep_second(spaced("Starting items:"), int_list);
-        let p_op = keep_second(spaced("Operation: new = old "), ops.clone());
+/// returns the value parsed from a whole line, rejecting any unconsumed input
+fn complete<T, E: fmt::Display>(result: Result<(&str, T), E>) -> Result<T, String> {
+    match result {
+        Ok(("", value)) => Ok(value),
+        Ok((rest, _)) => Err(format!("Unexpected input: '{}'", rest)),
+        Err(err) => Err(format!("{}", err)),
+    }
+}
+
+/// parses a monkey description, reporting errors against line numbers counted from `first_line`
+fn parse_monkey(value: &str, first_line: usize) -> Result<Monkey, String> {
+    let digits = one_or_more(digit_char(10)).map::<String>(|s| s.into_iter().collect());
+    // signs are accepted here and numbers checked after parsing, so negative or overflowing
+    // values can be reported clearly rather than wrapping
+    let number = one_or_more(choice([p_char('-'), digit_char(10)])).map(|s| {
+        let value: String = s.into_iter().collect();
+        value
+            .parse::<usize>()
+            .map_err(|_| format!("Number out of range: '{}'", value))
+    });
+    let comma = p_char(',').and_then(spaces());
 
-        let string_then_int = |s| keep_second(spaced(s), spaced_int.clone()).map(|i| i as usize);
-        let p_test = string_then_int("Test: divisible by");
-        let p_true = string_then_int("If true: throw to monkey");
-        let p_false = string_then_int("If false: throw to monkey");
+    let spaced = |s| between(spaces(), p_string(s), spaces());
 
-        let lines: Vec<_> = value.lines().collect();
+    let int_list = sep_by(number.clone(), comma);
+    let spaced_int = between(spaces(), number.clone(), spaces());
+    // numbers are checked after parsing, so one too large for usize can be reported
+    let operand = choice([p_string("old"), digits]).map(|value| match value.as_str() {
+        "old" => Ok(Operand::Old),
+        _ => value
+            .parse()
+            .map(Operand::Value)
+            .map_err(|_| format!("Operand out of range: '{}'", value)),
+    });
+    let mult = p_char('*').map::<fn(_) -> _>(|_| Operation::Multiply);
+    let plus = p_char('+').map::<fn(_) -> _>(|_| Operation::Add);
+    let ops = choice::<fn(_) -> _>([plus, mult])
+        .and_then(between(spaces(), operand, spaces()))
+        .map(|(op, operand)| operand.map(op));
 
-        let items = p_items.parse(lines[1]).map_err(|err| format!("{}", err))?.1;
-        let op_fn = p_op.parse(lines[2]).map_err(|err| format!("{}", err))?.1;
-        let test = p_test.parse(lines[3]).map_err(|err| format!("{}", err))?.1;
+    let p_id =
+        between(p_string("Monkey "), number, p_char(':')).with_label("monkey header".to_string());
+    let p_items =
+        keep_second(spaced("Starting items:"), int_list).with_label("starting items".to_string());
+    let p_op =
+        keep_second(spaced("Operation: new = old "), ops).with_label("operation".to_string());
 
-        let on_true = p_true.parse(lines[4]).map_err(|err| format!("{}", err))?.1;
-        let on_false = p_false.parse(lines[5]).map_err(|err| format!("{}", err))?.1;
-        let target = (on_true, on_false);
+    let string_then_int =
+        |s, label: &str| keep_second(spaced(s), spaced_int.clone()).with_label(label.to_string());
+    let p_test = string_then_int("Test: divisible by", "test");
+    let p_true = string_then_int("If true: throw to monkey", "true target");
+    let p_false = string_then_int("If false: throw to monkey", "false target");
+
+    let lines: Vec<_> = value.lines().collect();
+    let line = |offset: usize| {
+        lines
+            .get(offset)
+            .copied()
+            .ok_or_else(|| "Missing line".to_string())
+    };
+    let at = |offset: usize| move |err: String| format!("line {}: {}", first_line + offset, err);
+
+    let id = line(0)
+        .and_then(|l| complete(p_id.parse(l)))
+        .and_then(|id| id)
+        .map_err(at(0))?;
+    let items = line(1)
+        .and_then(|l| complete(p_items.parse(l)))
+        .and_then(|items| items.into_iter().collect())
+        .map_err(at(1))?;
+    let op_fn = line(2)
+        .and_then(|l| complete(p_op.parse(l)))
+        .and_then(|op_fn| op_fn)
+        .map_err(at(2))?;
+    let test = line(3)
+        .and_then(|l| complete(p_test.parse(l)))
+        .and_then(|test| test)
+        .map_err(at(3))?;
+    if test == 0 {
+        return Err(at(3)("Test divisor must not be 0".to_string()));
+    }
 
-        Ok(Monkey::new(items, op_fn, test, target))
+    let on_true = line(4)
+        .and_then(|l| complete(p_true.parse(l)))
+        .and_then(|on_true| on_true)
+        .map_err(at(4))?;
+    let on_false = line(5)
+        .and_then(|l| complete(p_false.parse(l)))
+        .and_then(|on_false| on_false)
+        .map_err(at(5))?;
+    let target = (on_true, on_false);
+    if lines.len() > 6 {
+        return Err(at(6)(format!("Unexpected line: '{}'", lines[6])));
+    }
+
+    Ok(Monkey::new(id, items, op_fn, test, target))
+}
+
+impl FromStr for Monkey {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse_monkey(value, 1)
+    }
+}
+
+/// Something that happens during a round, as described in the puzzle
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// monkey with this id starts its turn
+    Turn(usize),
+    /// an item with this worry level is inspected
+    Inspect(usize),
+    /// the operation raised the worry level to this value
+    Operation(Operation, usize),
+    /// relief divided the worry level down to this value
+    Bored(usize),
+    /// the worry level was tested against the divisor
+    Test {
+        /// monkey's test divisor
+        divisor: usize,
+        /// true if the worry level was divisible
+        passed: bool,
+    },
+    /// the item was thrown to another monkey
+    Throw {
+        /// worry level of the item
+        item: usize,
+        /// id of the catching monkey
+        target: usize,
+    },
+}
+
+// matches the worked example in the puzzle text
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Turn(id) => write!(f, "Monkey {}:", id),
+            Self::Inspect(item) => write!(
+                f,
+                "  Monkey inspects an item with a worry level of {}.",
+                item
+            ),
+            Self::Operation(operation, result) => {
+                let (verb, operand) = match operation {
+                    Operation::Add(operand) => ("increases by", operand),
+                    Operation::Multiply(operand) => ("is multiplied by", operand),
+                };
+                let operand = match operand {
+                    Operand::Old => "itself".to_string(),
+                    Operand::Value(value) => value.to_string(),
+                };
+                write!(f, "    Worry level {} {} to {}.", verb, operand, result)
+            }
+            Self::Bored(item) => write!(
+                f,
+                "    Monkey gets bored with item. Worry level is divided by 3 to {}.",
+                item
+            ),
+            Self::Test { divisor, passed } => write!(
+                f,
+                "    Current worry level is {}divisible by {}.",
+                if passed { "" } else { "not " },
+                divisor
+            ),
+            Self::Throw { item, target } => write!(
+                f,
+                "    Item with worry level {} is thrown to monkey {}.",
+                item, target
+            ),
+        }
     }
 }
 
 type State = (Vec<Monkey>, Vec<usize>);
 
-fn play_turn((mut monkeys, mut count): State) -> State {
+/// plays one round, reporting each step to `on_event`
+fn play_turn<F>((mut monkeys, mut count): State, mut on_event: F) -> State
+where
+    F: FnMut(Event),
+{
     for idx in 0..monkeys.len() {
+        on_event(Event::Turn(monkeys[idx].id));
         let used: Vec<_> = monkeys[idx].items.drain(..).collect();
         used.into_iter().for_each(|item| {
             // cant use the iterator directly without a double mutable borrow
-            let new_value = (monkeys[idx].operation)(item) / 3;
-            let destination = if new_value % monkeys[idx].test == 0 {
-                monkeys[idx].target.0
+            let monkey = &monkeys[idx];
+            on_event(Event::Inspect(item));
+            let inspected = monkey.operation.apply(item);
+            on_event(Event::Operation(monkey.operation, inspected));
+            let new_value = inspected / 3;
+            on_event(Event::Bored(new_value));
+            let passed = new_value % monkey.test == 0;
+            on_event(Event::Test {
+                divisor: monkey.test,
+                passed,
+            });
+            let destination = if passed {
+                monkey.target.0
             } else {
-                monkeys[idx].target.1
+                monkey.target.1
             };
+            on_event(Event::Throw {
+                item: new_value,
+                target: destination,
+            });
 
             count[idx] += 1;
             monkeys[destination].items.push(new_value);
@@ -111,19 +332,92 @@ fn play_turn((mut monkeys, mut count): State) -> State {
     (monkeys, count)
 }
 
+/// returns the items held by each monkey after `round`, as in the puzzle text
+fn summary(round: usize, monkeys: &[Monkey]) -> String {
+    let mut result = format!(
+        "After round {}, the monkeys are holding items with these worry levels:",
+        round
+    );
+    for monkey in monkeys {
+        let items: Vec<_> = monkey.items.iter().map(ToString::to_string).collect();
+        result.push_str(&format!("\nMonkey {}: {}", monkey.id, items.join(", ")));
+    }
+
+    result
+}
+
+/// parses every monkey, checking they are listed in order and only throw to other monkeys
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, String> {
+    let mut monkeys = Vec::new();
+    let mut first_lines = Vec::new();
+    let mut first_line = 1;
+    for (idx, block) in input.trim_end().split("\n\n").enumerate() {
+        let monkey =
+            parse_monkey(block, first_line).map_err(|err| format!("Monkey {}, {}", idx, err))?;
+        if monkey.id != idx {
+            return Err(format!(
+                "Monkey {}, line {}: expected monkey {}, found monkey {}",
+                idx, first_line, idx, monkey.id
+            ));
+        }
+
+        monkeys.push(monkey);
+        first_lines.push(first_line);
+        // skip the blank separator line too
+        first_line += block.lines().count() + 1;
+    }
+
+    for (monkey, first_line) in monkeys.iter().zip(first_lines) {
+        let (on_true, on_false) = monkey.target;
+        for (target, offset) in [(on_true, 4), (on_false, 5)] {
+            let problem = if target == monkey.id {
+                "throws to itself".to_string()
+            } else if target >= monkeys.len() {
+                format!("throws to missing monkey {}", target)
+            } else {
+                continue;
+            };
+
+            return Err(format!(
+                "Monkey {}, line {}: {}",
+                monkey.id,
+                first_line + offset,
+                problem
+            ));
+        }
+    }
+
+    Ok(monkeys)
+}
+
+/// returns the detailed event log of each of the first `rounds` rounds, each followed by a summary
+/// of the items held at its end
+pub fn trace(file_path: &str, rounds: usize) -> String {
+    let mut monkeys = parse_monkeys(&read_file(file_path)).unwrap();
+    let mut counts = vec![0; monkeys.len()];
+    let mut result = Vec::new();
+
+    for round in 1..=rounds {
+        (monkeys, counts) = play_turn((monkeys, counts), |event| {
+            result.push(event.to_string());
+        });
+        result.push(String::new());
+        result.push(summary(round, &monkeys));
+        result.push(String::new());
+    }
+
+    result.join("\n")
+}
+
 /// returns the product of the two largest inspection counts after 20 rounds
 pub fn one(file_path: &str) -> usize {
     const ROUNDS: usize = 20;
 
-    let mut monkeys: Vec<Monkey> = read_file(file_path)
-        .split("\n\n")
-        .map(FromStr::from_str)
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+    let mut monkeys = parse_monkeys(&read_file(file_path)).unwrap();
     let mut counts = vec![0; monkeys.len()];
 
     for _ in 0..ROUNDS {
-        (monkeys, counts) = play_turn((monkeys, counts));
+        (monkeys, counts) = play_turn((monkeys, counts), |_| ());
     }
 
     counts.sort();
@@ -142,4 +436,175 @@ mod test {
         let actual = one("input/11-t.txt");
         assert_eq!(actual, expected, "{}", msg);
     }
+
+    #[test]
+    fn round_trip() {
+        let msg = "should format a monkey as in the puzzle input";
+        let input = read_file("input/11-t.txt");
+        for block in input.trim_end().split("\n\n") {
+            let monkey: Monkey = block.parse().unwrap();
+            assert_eq!(monkey.to_string(), block, "{}", msg);
+            assert_eq!(monkey.to_string().parse(), Ok(monkey), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn operation() {
+        let msg = "should apply the operation to the worry level";
+        let monkeys: Vec<Monkey> = read_file("input/11-t.txt")
+            .split("\n\n")
+            .map(|block| block.parse().unwrap())
+            .collect();
+        let expected = [
+            Operation::Multiply(Operand::Value(19)),
+            Operation::Add(Operand::Value(6)),
+            Operation::Multiply(Operand::Old),
+            Operation::Add(Operand::Value(3)),
+        ];
+        for (monkey, expected) in monkeys.iter().zip(expected) {
+            assert_eq!(monkey.operation, expected, "{}", msg);
+        }
+        assert_eq!(monkeys[0].operation.apply(79), 1501, "{}", msg);
+        assert_eq!(monkeys[2].operation.apply(79), 6241, "{}", msg);
+    }
+
+    #[test]
+    fn round_trace() {
+        let msg = "should match the worked example in the puzzle text";
+        let actual = trace("input/11-t.txt", 2);
+        let lines: Vec<_> = actual.lines().collect();
+        let expected = [
+            "Monkey 0:",
+            "  Monkey inspects an item with a worry level of 79.",
+            "    Worry level is multiplied by 19 to 1501.",
+            "    Monkey gets bored with item. Worry level is divided by 3 to 500.",
+            "    Current worry level is not divisible by 23.",
+            "    Item with worry level 500 is thrown to monkey 3.",
+            "  Monkey inspects an item with a worry level of 98.",
+            "    Worry level is multiplied by 19 to 1862.",
+            "    Monkey gets bored with item. Worry level is divided by 3 to 620.",
+            "    Current worry level is not divisible by 23.",
+            "    Item with worry level 620 is thrown to monkey 3.",
+            "Monkey 1:",
+            "  Monkey inspects an item with a worry level of 54.",
+            "    Worry level increases by 6 to 60.",
+            "    Monkey gets bored with item. Worry level is divided by 3 to 20.",
+            "    Current worry level is not divisible by 19.",
+            "    Item with worry level 20 is thrown to monkey 0.",
+        ];
+        assert_eq!(lines[..expected.len()], expected, "{}", msg);
+
+        let expected = [
+            "Monkey 2:",
+            "  Monkey inspects an item with a worry level of 79.",
+            "    Worry level is multiplied by itself to 6241.",
+            "    Monkey gets bored with item. Worry level is divided by 3 to 2080.",
+            "    Current worry level is divisible by 13.",
+            "    Item with worry level 2080 is thrown to monkey 1.",
+        ];
+        let start = lines.iter().position(|&line| line == "Monkey 2:").unwrap();
+        assert_eq!(lines[start..start + expected.len()], expected, "{}", msg);
+
+        let expected = "\
+After round 1, the monkeys are holding items with these worry levels:
+Monkey 0: 20, 23, 27, 26
+Monkey 1: 2080, 25, 167, 207, 401, 1046
+Monkey 2: \n\
+Monkey 3: ";
+        assert!(actual.contains(expected), "{}", msg);
+        let expected = "\
+After round 2, the monkeys are holding items with these worry levels:
+Monkey 0: 695, 10, 71, 135, 350
+Monkey 1: 43, 49, 58, 55, 362
+Monkey 2: \n\
+Monkey 3: ";
+        assert!(actual.contains(expected), "{}", msg);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let msg = "should return an error naming the monkey and line";
+        let input = read_file("input/11-t.txt");
+        let cases = [
+            // short block
+            (
+                input.replacen("    If false: throw to monkey 0\n", "", 1),
+                "Monkey 1, line 13: Missing line",
+            ),
+            // header out of order
+            (
+                input.replacen("Monkey 1:", "Monkey 2:", 1),
+                "Monkey 1, line 8: expected monkey 1, found monkey 2",
+            ),
+            // throw to self
+            (
+                input.replacen(
+                    "If true: throw to monkey 2",
+                    "If true: throw to monkey 0",
+                    1,
+                ),
+                "Monkey 0, line 5: throws to itself",
+            ),
+            // throw to a missing monkey
+            (
+                input.replacen(
+                    "If false: throw to monkey 1",
+                    "If false: throw to monkey 4",
+                    1,
+                ),
+                "Monkey 3, line 27: throws to missing monkey 4",
+            ),
+            // operand too large for usize
+            (
+                input.replacen("old * 19", "old * 99999999999999999999999", 1),
+                "Monkey 0, line 3: Operand out of range: '99999999999999999999999'",
+            ),
+            // negative divisor
+            (
+                input.replacen("divisible by 19", "divisible by -3", 1),
+                "Monkey 1, line 11: Number out of range: '-3'",
+            ),
+            // negative target
+            (
+                input.replacen(
+                    "If true: throw to monkey 2",
+                    "If true: throw to monkey -1",
+                    1,
+                ),
+                "Monkey 0, line 5: Number out of range: '-1'",
+            ),
+            // negative item
+            (
+                input.replacen("Starting items: 79, 98", "Starting items: 79, -3", 1),
+                "Monkey 0, line 2: Number out of range: '-3'",
+            ),
+            // negative id
+            (
+                input.replacen("Monkey 0:", "Monkey -1:", 1),
+                "Monkey 0, line 1: Number out of range: '-1'",
+            ),
+            // item too large for usize
+            (
+                input.replacen(
+                    "Starting items: 79, 98",
+                    "Starting items: 79, 99999999999999999999999",
+                    1,
+                ),
+                "Monkey 0, line 2: Number out of range: '99999999999999999999999'",
+            ),
+            // trailing input
+            (
+                input.replacen("Test: divisible by 19", "Test: divisible by 19x", 1),
+                "Monkey 1, line 11: Unexpected input: 'x'",
+            ),
+        ];
+        for (input, expected) in cases {
+            let actual = parse_monkeys(&input);
+            assert_eq!(actual, Err(expected.to_string()), "{}", msg);
+        }
+
+        let msg = "should name the monkey and line of a malformed header";
+        let actual = parse_monkeys(&input.replacen("Monkey 2:", "Monkey two:", 1)).unwrap_err();
+        assert!(actual.starts_with("Monkey 2, line 15: "), "{}", msg);
+    }
 }
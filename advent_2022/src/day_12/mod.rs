@@ -1,134 +1,313 @@
 //! Solutions to 2020 day 12 problems
 //! --- Day 12: Hill Climbing Algorithm ---
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+use std::str::FromStr;
 
-use crate::{day_03::LOWERCASE_OFFSET, day_09::Direction, read_file};
+use crate::{
+    day_03::LOWERCASE_OFFSET,
+    geometry::{Direction, EAST, NORTH, SOUTH, WEST},
+    read_file,
+    search::{astar, bfs, Paths},
+    Grid,
+};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct Height(u32);
 
-fn parse_char(character: char) -> (Height, bool, bool) {
-    let mut start = false;
-    let mut end = false;
-    let height = Height(match character {
-        'S' => {
-            start = true;
-            ('a' as u32).checked_sub(LOWERCASE_OFFSET).unwrap()
-        }
-        'E' => {
-            end = true;
-            ('z' as u32).checked_sub(LOWERCASE_OFFSET).unwrap()
-        }
-        _ => (character as u32)
-            .checked_sub(LOWERCASE_OFFSET)
-            .unwrap_or_else(|| panic!("Invalid character in map data: '{}'", character)),
-    });
+impl From<char> for Height {
+    fn from(character: char) -> Self {
+        let elevation = match character {
+            'S' => 'a',
+            'E' => 'z',
+            _ => character,
+        };
 
-    (height, start, end)
+        Self(
+            (elevation as u32)
+                .checked_sub(LOWERCASE_OFFSET)
+                .unwrap_or_else(|| panic!("Invalid character in map data: '{}'", character)),
+        )
+    }
 }
 
-fn parse_map(input: &str) -> Result<(HashMap<Direction, Height>, Direction, Direction), String> {
-    let mut result = HashMap::new();
-    let mut start = Default::default();
-    let mut end = Default::default();
-
-    for (row, line) in input.lines().enumerate() {
-        for (col, ch) in line.chars().enumerate() {
-            let (height, is_start, is_end) = parse_char(ch);
-            let direction = Direction(col as isize, row as isize);
-
-            result.insert(direction, height);
+/// limits on the change in elevation of a single step
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rules {
+    /// greatest rise allowed in one step
+    pub max_climb: u32,
+    /// greatest drop allowed in one step
+    pub max_descent: u32,
+}
 
-            if is_start {
-                start = direction;
-            }
-            if is_end {
-                end = direction;
-            }
+impl Default for Rules {
+    /// climb at most one level, and drop any distance
+    fn default() -> Self {
+        Self {
+            max_climb: 1,
+            max_descent: u32::MAX,
         }
     }
+}
+
+impl Rules {
+    /// returns true if a step from elevation `from` to elevation `to` is allowed
+    pub fn allows(&self, from: u32, to: u32) -> bool {
+        to <= from.saturating_add(self.max_climb) && from <= to.saturating_add(self.max_descent)
+    }
+}
 
-    Ok((result, start, end))
+/// Puzzle heightmap, with its marked start and end positions
+#[derive(Clone, Debug)]
+pub struct HeightMap {
+    heights: Grid<Height>,
+    start: Direction,
+    end: Direction,
+    rules: Rules,
 }
 
-const WEST: Direction = Direction(-1, 0);
-const EAST: Direction = Direction(1, 0);
-const NORTH: Direction = Direction(0, 1);
-const SOUTH: Direction = Direction(0, -1);
+impl FromStr for HeightMap {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let chars: Grid<char> = input.parse()?;
+        let find = |target| {
+            chars
+                .iter()
+                .find(|&(_, &ch)| ch == target)
+                .map(|(position, _)| position)
+                .ok_or_else(|| format!("Map has no '{}' position", target))
+        };
+        let start = find('S')?;
+        let end = find('E')?;
 
-fn get_neighbors(current: Direction) -> Vec<Direction> {
-    vec![
-        current + NORTH,
-        current + SOUTH,
-        current + EAST,
-        current + WEST,
-    ]
+        Ok(Self {
+            heights: chars.map(|&ch| Height::from(ch)),
+            start,
+            end,
+            rules: Default::default(),
+        })
+    }
 }
 
-fn dijkstra(map: &HashMap<Direction, Height>, target: Direction) -> HashMap<Direction, u32> {
-    let mut result: HashMap<Direction, u32> = Default::default();
-    let mut visited: HashSet<Direction> = Default::default();
-    let mut queue = VecDeque::new();
+impl HeightMap {
+    /// Returns the map with its stepping rules replaced by `rules`
+    pub fn with_rules(self, rules: Rules) -> Self {
+        Self { rules, ..self }
+    }
+
+    /// Returns the position marked `S`
+    pub fn start(&self) -> Direction {
+        self.start
+    }
+
+    /// Returns the position marked `E`
+    pub fn end(&self) -> Direction {
+        self.end
+    }
+
+    /// Returns the number of columns
+    pub fn width(&self) -> usize {
+        self.heights.width()
+    }
+
+    /// Returns the number of rows
+    pub fn height(&self) -> usize {
+        self.heights.height()
+    }
 
-    queue.push_back(target);
-    result.insert(target, 0);
+    /// Returns the elevation at `position`, from 1 for `a` to 26 for `z`, or None if out of bounds
+    pub fn elevation(&self, position: Direction) -> Option<u32> {
+        self.heights.get(position).map(|height| height.0)
+    }
 
-    while !queue.is_empty() {
-        let current = queue.pop_front().unwrap();
-        if !visited.insert(current) {
-            continue;
+    /// Returns the positions reachable in one step from `position`, none if it is out of bounds
+    pub fn neighbors(&self, position: Direction) -> impl Iterator<Item = Direction> + '_ {
+        let from = self.elevation(position);
+        self.heights
+            .neighbors(position)
+            .filter(move |(_, to)| matches!(from, Some(from) if self.rules.allows(from, to.0)))
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// Returns the positions that can reach `position` in one step
+    fn predecessors(&self, position: Direction) -> impl Iterator<Item = Direction> + '_ {
+        let to = self.elevation(position);
+        self.heights
+            .neighbors(position)
+            .filter(move |(_, from)| matches!(to, Some(to) if self.rules.allows(from.0, to)))
+            .map(|(neighbor, _)| neighbor)
+    }
+
+    /// Returns the paths from every position that can reach `target`, searching backwards from it
+    fn paths_to(&self, target: Direction) -> Paths<Direction> {
+        bfs(target, |current| self.predecessors(current))
+    }
+
+    /// Returns the fewest steps from `from` to `to`, if both are on the map and `to` is reachable
+    pub fn distance(&self, from: Direction, to: Direction) -> Option<u32> {
+        if !self.heights.contains(from) || !self.heights.contains(to) {
+            return None;
         }
 
-        let distance = result.get(&current).unwrap() + 1;
-        for neighbor in get_neighbors(current) {
-            if map.get(&neighbor).is_none() || visited.contains(&neighbor) {
-                continue;
-            }
-
-            // if the height makes it non-traversable, continue
-            let current_height = map.get(&current).unwrap().0;
-            let neighbor_height = map.get(&neighbor).unwrap().0;
-            if current_height > neighbor_height && (current_height - neighbor_height) > 1 {
-                continue;
-            }
-
-            result.insert(neighbor, distance);
-            queue.push_back(neighbor);
+        astar(
+            from,
+            |current| self.neighbors(current).map(|neighbor| (neighbor, 1)),
+            |current| current == to,
+            |current| current.manhattan(&to) as u32,
+        )
+        .map(|(distance, _)| distance)
+    }
+
+    /// Returns the fewest steps to `to` from any position whose position and elevation satisfy
+    /// `is_start`, if `to` is on the map and any of them can reach it
+    pub fn distance_from<P>(&self, is_start: P, to: Direction) -> Option<u32>
+    where
+        P: Fn(Direction, u32) -> bool,
+    {
+        if !self.heights.contains(to) {
+            return None;
         }
+
+        let paths = self.paths_to(to);
+
+        self.heights
+            .iter()
+            .filter(|&(position, height)| is_start(position, height.0))
+            .filter_map(|(position, _)| paths.distance(position))
+            .min()
     }
 
-    result
+    /// Returns the positions along a shortest path from `from` to `to`, inclusive, if both are on
+    /// the map and `to` is reachable
+    pub fn path(&self, from: Direction, to: Direction) -> Option<Vec<Direction>> {
+        if !self.heights.contains(from) || !self.heights.contains(to) {
+            return None;
+        }
+
+        // the search runs backwards from the end, so its paths lead from the end to the start
+        let mut path = self.paths_to(to).path(from)?;
+        path.reverse();
+
+        Some(path)
+    }
 }
 
 /// returns the shortest path length from start to end position
 pub fn one(file_path: &str) -> u32 {
-    let input = read_file(file_path);
-    let (map, start, end) = parse_map(&input).unwrap();
-    let distance_map = dijkstra(&map, end);
+    let map: HeightMap = read_file(file_path).parse().unwrap();
 
-    *distance_map.get(&start).unwrap()
+    map.distance(map.start(), map.end()).unwrap()
 }
 
 /// returns the shortest path length from any square with elevation a to the end
 /// position
 pub fn two(file_path: &str) -> u32 {
-    let input = read_file(file_path);
-    let (map, _, end) = parse_map(&input).unwrap();
-    let distance_map = dijkstra(&map, end);
-
-    *map.iter()
-        .filter_map(|(position, &height)| {
-            if height.0 == 1 {
-                // some of the map points may be non-traversable
-                Some(distance_map.get(position).unwrap_or(&u32::MAX))
-            } else {
-                None
-            }
-        })
-        .min()
+    let map: HeightMap = read_file(file_path).parse().unwrap();
+
+    map.distance_from(|_, elevation| elevation == 1, map.end())
         .unwrap()
 }
 
+/// returns the positions along a shortest path from the start to the end position, if one exists
+pub fn route(file_path: &str) -> Option<Vec<Direction>> {
+    let map: HeightMap = read_file(file_path).parse().unwrap();
+
+    map.path(map.start(), map.end())
+}
+
+fn arrow(step: Direction) -> char {
+    match step {
+        NORTH => '^',
+        SOUTH => 'v',
+        WEST => '<',
+        EAST => '>',
+        _ => panic!("Path step is not to an adjacent position: {:?}", step),
+    }
+}
+
+/// draws `path` on a blank map as in the puzzle text: each position points toward the next, and
+/// the last is marked `E`
+fn draw_path(width: usize, height: usize, path: &[Direction]) -> Grid<char> {
+    let mut result = Grid::new(width, height, '.');
+    for pair in path.windows(2) {
+        result[pair[0]] = arrow(pair[1] - pair[0]);
+    }
+    if let Some(&end) = path.last() {
+        result[end] = 'E';
+    }
+
+    result
+}
+
+/// returns the map with a shortest path from the start to the end position drawn on it
+pub fn render(file_path: &str) -> String {
+    let map: HeightMap = read_file(file_path).parse().unwrap();
+    let path = map.path(map.start(), map.end()).unwrap_or_default();
+
+    draw_path(map.width(), map.height(), &path).to_string()
+}
+
+/// cell coloring for [`to_ppm`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shading {
+    /// dark green at the lowest elevation through to white at the highest
+    Elevation,
+    /// blue nearest the end position through to red furthest from it. Positions that cannot reach
+    /// the end are black.
+    Distance,
+}
+
+type Rgb = [u8; 3];
+
+const PATH_COLOR: Rgb = [255, 255, 0];
+
+/// returns the color `amount` of the way from `from` to `to`, where `total` is the whole way
+fn blend(from: Rgb, to: Rgb, amount: u32, total: u32) -> Rgb {
+    let total = total.max(1);
+    let mut result = from;
+    for (channel, (&start, &end)) in result.iter_mut().zip(from.iter().zip(to.iter())) {
+        let (start, end) = (start as i64, end as i64);
+        *channel = (start + (end - start) * amount as i64 / total as i64) as u8;
+    }
+
+    result
+}
+
+/// returns the map as a plain text PPM image with one pixel per position, and a shortest path
+/// from the start to the end position drawn in yellow
+pub fn to_ppm(file_path: &str, shading: Shading) -> String {
+    let map: HeightMap = read_file(file_path).parse().unwrap();
+    let paths = map.paths_to(map.end());
+    let furthest = paths.distances.values().copied().max().unwrap_or(0);
+    let highest = map
+        .heights
+        .iter()
+        .map(|(_, height)| height.0)
+        .max()
+        .unwrap_or(0);
+    let mut pixels = map.heights.map(|&height| match shading {
+        Shading::Elevation => blend([0, 80, 0], [255, 255, 255], height.0, highest),
+        // filled in below, since distance depends on position rather than height
+        Shading::Distance => [0, 0, 0],
+    });
+    if shading == Shading::Distance {
+        for (&position, &distance) in &paths.distances {
+            pixels[position] = blend([0, 0, 255], [255, 0, 0], distance, furthest);
+        }
+    }
+    for position in map.path(map.start(), map.end()).unwrap_or_default() {
+        pixels[position] = PATH_COLOR;
+    }
+
+    // one pixel per line keeps within the format's 70 character line limit
+    let mut result = format!("P3\n{} {}\n255\n", map.width(), map.height());
+    for (_, [r, g, b]) in pixels.iter() {
+        writeln!(result, "{} {} {}", r, g, b).unwrap();
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -148,4 +327,118 @@ mod test {
         let actual = two("input/12-t.txt");
         assert_eq!(actual, expected, "{}", msg);
     }
+
+    #[test]
+    fn shortest_route() {
+        let msg = "should return the positions along a shortest path from start to end";
+        let map: HeightMap = read_file("input/12-t.txt").parse().unwrap();
+        let actual = route("input/12-t.txt").unwrap();
+        assert_eq!(actual.len(), 32, "{}", msg);
+        assert_eq!(actual.first(), Some(&map.start()), "{}", msg);
+        assert_eq!(actual.last(), Some(&map.end()), "{}", msg);
+        for pair in actual.windows(2) {
+            assert_eq!(pair[0].manhattan(&pair[1]), 1, "{}", msg);
+            assert!(
+                map.neighbors(pair[0]).any(|next| next == pair[1]),
+                "{}",
+                msg
+            );
+        }
+    }
+
+    #[test]
+    fn render_path() {
+        let msg = "should draw the path with arrows toward the end";
+        let actual = render("input/12-t.txt");
+        let arrows = actual.chars().filter(|ch| "^v<>".contains(*ch)).count();
+        assert_eq!(arrows, 31, "{}", msg);
+        assert_eq!(actual.lines().count(), 5, "{}", msg);
+        assert_eq!(
+            actual.lines().nth(2).unwrap().chars().nth(5),
+            Some('E'),
+            "{}",
+            msg
+        );
+        assert!("v>".contains(actual.chars().next().unwrap()), "{}", msg);
+
+        let path = [
+            Direction(0, 0),
+            Direction(1, 0),
+            Direction(1, 1),
+            Direction(0, 1),
+        ];
+        let expected = ">v\nE<";
+        assert_eq!(draw_path(2, 2, &path).to_string(), expected, "{}", msg);
+    }
+
+    #[test]
+    fn ppm() {
+        let msg = "should encode the map as a PPM image";
+        for shading in [Shading::Elevation, Shading::Distance] {
+            let actual = to_ppm("input/12-t.txt", shading);
+            let lines: Vec<_> = actual.lines().collect();
+            assert_eq!(&lines[..3], ["P3", "8 5", "255"], "{}", msg);
+            assert_eq!(lines.len(), 3 + 8 * 5, "{}", msg);
+            // the start is on the path
+            assert_eq!(lines[3], "255 255 0", "{}", msg);
+        }
+        let actual = blend([0, 80, 0], [255, 255, 255], 13, 26);
+        assert_eq!(actual, [127, 167, 127], "{}", msg);
+    }
+
+    #[test]
+    fn queries() {
+        let msg = "should return the fewest steps between any two positions";
+        let map: HeightMap = read_file("input/12-t.txt").parse().unwrap();
+        assert_eq!(map.distance(map.start(), map.start()), Some(0), "{}", msg);
+        assert_eq!(
+            map.distance(Direction(0, 4), map.end()),
+            Some(29),
+            "{}",
+            msg
+        );
+        // descending is unrestricted, so the way back is direct
+        assert_eq!(map.distance(map.end(), map.start()), Some(7), "{}", msg);
+
+        let msg = "should return the fewest steps from any matching start position";
+        // from the 'm' at the right end of the top row
+        let actual = map.distance_from(|position, _| position.1 == 0, map.end());
+        assert_eq!(actual, Some(12), "{}", msg);
+        let actual = map.distance_from(|_, elevation| elevation == 1, map.end());
+        assert_eq!(actual, Some(29), "{}", msg);
+
+        let msg = "should return None for positions outside the map";
+        let (outside, far) = (Direction(-1, 0), Direction(100, 100));
+        assert_eq!(map.distance(outside, map.end()), None, "{}", msg);
+        assert_eq!(map.distance(map.start(), far), None, "{}", msg);
+        assert_eq!(map.distance_from(|_, _| true, far), None, "{}", msg);
+        assert_eq!(map.path(map.start(), far), None, "{}", msg);
+        assert_eq!(map.path(outside, map.end()), None, "{}", msg);
+        assert_eq!(map.neighbors(outside).count(), 0, "{}", msg);
+    }
+
+    #[test]
+    fn rules() {
+        let msg = "should limit each step by the configured climb and descent";
+        let rules = Rules {
+            max_climb: 1,
+            max_descent: 1,
+        };
+        assert!(rules.allows(3, 4), "{}", msg);
+        assert!(!rules.allows(3, 5), "{}", msg);
+        assert!(rules.allows(3, 2), "{}", msg);
+        assert!(!rules.allows(3, 1), "{}", msg);
+
+        let map: HeightMap = read_file("input/12-t.txt").parse().unwrap();
+        let (start, end) = (map.start(), map.end());
+        let map = map.with_rules(rules);
+        assert_eq!(map.distance(end, start), Some(31), "{}", msg);
+
+        let map = map.with_rules(Rules {
+            max_climb: 0,
+            max_descent: 0,
+        });
+        assert_eq!(map.distance(start, end), None, "{}", msg);
+        assert_eq!(map.distance(start, Direction(1, 0)), Some(1), "{}", msg);
+    }
 }
@@ -1,8 +1,13 @@
 //! Solutions to 2020 day 14 problems
 //! --- Day 14: Regolith Reservoir ---
 use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
 
-use crate::{day_09::Direction, read_file};
+use crate::{
+    geometry::{self, BoundingBox, Direction, SOUTH, SOUTH_EAST, SOUTH_WEST},
+    read_file, Grid,
+};
 
 fn parse_line(line: &str) -> HashSet<Direction> {
     let points: Vec<_> = line
@@ -13,130 +18,367 @@ fn parse_line(line: &str) -> HashSet<Direction> {
                 .unwrap()
         })
         .collect();
-    let mut result = HashSet::new();
-
-    for idx in 1..points.len() {
-        let start = points[idx - 1];
-        let end = points[idx];
-
-        if start.0 == end.0 {
-            // vertical
-            let x = start.0;
-            let min = start.1.min(end.1);
-            let max = start.1.max(end.1);
-            for y in min..=max {
-                result.insert(Direction(x, y));
-            }
-        } else {
-            // horizontal
-            let y = start.1;
-            let min = start.0.min(end.0);
-            let max = start.0.max(end.0);
-            for x in min..=max {
-                result.insert(Direction(x, y));
-            }
+
+    points
+        .windows(2)
+        .flat_map(|pair| geometry::line(pair[0], pair[1]))
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// where sand enters the cave
+const START: Direction = Direction(500, 0);
+
+/// Sand simulation settings
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Simulation {
+    /// positions sand enters the cave from, taking turns one grain at a time
+    pub sources: Vec<Direction>,
+    /// depth of the floor below the lowest rock, or None to let sand fall into the abyss
+    pub floor: Option<isize>,
+    /// moves a grain tries in order each time it falls. Every move must be downward.
+    pub probes: Vec<Direction>,
+}
+
+impl Default for Simulation {
+    /// the puzzle rules: one source at 500, 0, no floor, falling down, down left then down right
+    fn default() -> Self {
+        Self {
+            sources: vec![START],
+            floor: None,
+            probes: vec![SOUTH, SOUTH_WEST, SOUTH_EAST],
         }
     }
-
-    result
 }
 
-fn step(mut map: HashSet<Direction>, max_y: isize) -> (HashSet<Direction>, isize) {
-    const START: Direction = Direction(500, 0);
-    let can_move = |position| {
-        if !map.contains(&(position + Direction(0, 1))) {
-            // down
-            return Some(position + Direction(0, 1));
+impl Simulation {
+    /// builds the cave described by `input` under these settings
+    fn cave(&self, input: &str) -> Result<Cave, String> {
+        if self.sources.is_empty() {
+            return Err("Simulation has no sand sources".to_string());
+        }
+        if self.probes.is_empty() {
+            return Err("Simulation has no probes".to_string());
         }
-        if !map.contains(&(position + Direction(-1, 1))) {
-            // down left
-            return Some(position + Direction(-1, 1));
+        if let Some(probe) = self.probes.iter().find(|probe| probe.1 <= 0) {
+            return Err(format!("Probe {:?} does not move downward", probe));
         }
-        if !map.contains(&(position + Direction(1, 1))) {
-            // down right
-            return Some(position + Direction(1, 1));
+        if let Some(floor) = self.floor.filter(|&floor| floor < 0) {
+            return Err(format!("Floor depth {} is above the lowest rock", floor));
         }
 
-        None
-    };
+        let rocks: HashSet<Direction> = input.lines().flat_map(parse_line).collect();
+        let rock_bounds =
+            BoundingBox::from_points(rocks.iter().copied()).ok_or("Cave contains no rock")?;
+        let limit = rock_bounds.max.1 + self.floor.unwrap_or(0);
+        let bounds = self
+            .sources
+            .iter()
+            .fold(rock_bounds, |bounds, &source| bounds.include(source));
 
-    let mut current_pos = START;
-    while let Some(new_pos) = can_move(current_pos) {
-        current_pos = new_pos;
-        if current_pos.1 >= max_y {
-            return (map, current_pos.1);
+        // each move descends at least one row, so sand can only drift so far sideways before it
+        // settles on the floor or falls into the abyss
+        let drift = self
+            .probes
+            .iter()
+            .map(|probe| probe.0.abs())
+            .max()
+            .unwrap_or(0);
+        let reach = (limit - bounds.min.1.min(0)) * drift + 1;
+        let offset = Direction(bounds.min.0 - reach, bounds.min.1.min(0));
+        let width = bounds.width() as isize + 2 * reach;
+        let mut map = Grid::new(width as usize, (limit - offset.1 + 1) as usize, Tile::Air);
+        for rock in rocks {
+            map[rock - offset] = Tile::Rock;
         }
+
+        let mut cave = Cave {
+            map,
+            offset,
+            limit,
+            has_floor: self.floor.is_some(),
+            sources: self.sources.clone(),
+            probes: self.probes.clone(),
+            paths: Vec::new(),
+            turn: 0,
+        };
+        cave.paths = cave
+            .sources
+            .iter()
+            .map(|&source| {
+                if cave.is_free(source) {
+                    vec![source]
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
+
+        Ok(cave)
     }
-    map.insert(current_pos);
 
-    (map, current_pos.1)
+    /// returns the number of units of sand that come to rest in the cave described by `input`
+    /// before one falls into the abyss, or every source is blocked
+    pub fn run(&self, input: &str) -> Result<u32, String> {
+        Ok(self.cave(input)?.run(|_, _| ()))
+    }
 }
 
-/// returns the number of units of sand come to rest before sand starts flowing into the abyss below
-pub fn one(file_path: &str) -> u32 {
-    let mut map: HashSet<Direction> = read_file(file_path).lines().flat_map(parse_line).collect();
-    let max_y = map.iter().fold(0, |acc, next| next.1.max(acc));
-    let mut count = 0;
+/// what became of a dropped grain
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Outcome {
+    Rested(Direction),
+    Fell,
+    Blocked,
+}
+
+/// A cave with sand falling into it
+#[derive(Clone, Debug)]
+struct Cave {
+    map: Grid<Tile>,
+    /// cave position of the top left cell of `map`
+    offset: Direction,
+    /// row of the floor, or the lowest rock if there is no floor
+    limit: isize,
+    has_floor: bool,
+    sources: Vec<Direction>,
+    probes: Vec<Direction>,
+    /// route of the last grain from each source
+    paths: Vec<Vec<Direction>>,
+    /// index of the next source to drop from
+    turn: usize,
+}
 
-    let mut sand_height = 0;
-    while sand_height < max_y {
-        count += 1;
-        (map, sand_height) = step(map, max_y);
+impl Cave {
+    fn get(&self, position: Direction) -> Tile {
+        // sand that leaves the grid is falling into the abyss
+        self.map
+            .get(position - self.offset)
+            .copied()
+            .unwrap_or(Tile::Air)
     }
 
-    count - 1
-}
+    fn is_free(&self, position: Direction) -> bool {
+        !(self.has_floor && position.1 >= self.limit) && self.get(position) == Tile::Air
+    }
 
-fn step_two(mut map: HashSet<Direction>, max_y: isize) -> (HashSet<Direction>, bool) {
-    const START: Direction = Direction(500, 0);
-    let can_move = |position: Direction| {
-        let next = position + Direction(0, 1);
-        if next.1 >= max_y {
-            return None;
-        }
+    /// returns the first free position a grain at `position` can fall to
+    fn fall(&self, position: Direction) -> Option<Direction> {
+        self.probes
+            .iter()
+            .map(|&probe| position + probe)
+            .find(|&next| self.is_free(next))
+    }
 
-        if !map.contains(&next) {
-            // down
-            return Some(next);
+    /// drops one grain from source `idx`.
+    ///
+    /// The source's path holds the route of its previous grain. Everything on it above the last
+    /// resting place is still free, unless sand from another source has landed there since, so
+    /// the next grain resumes from its top instead of falling all the way from the source again.
+    fn drop_from(&mut self, idx: usize) -> Outcome {
+        let mut path = std::mem::take(&mut self.paths[idx]);
+        // filling a position on the path fills everything below it on the path too
+        while matches!(path.last(), Some(&top) if !self.is_free(top)) {
+            path.pop();
         }
 
-        let next = position + Direction(-1, 1);
-        if !map.contains(&next) {
-            // down left
-            return Some(next);
-        }
+        let outcome = loop {
+            let current = match path.last() {
+                Some(&current) => current,
+                None => break Outcome::Blocked,
+            };
+            if !self.has_floor && current.1 >= self.limit {
+                break Outcome::Fell;
+            }
+
+            match self.fall(current) {
+                Some(next) => path.push(next),
+                None => {
+                    self.map[current - self.offset] = Tile::Sand;
+                    path.pop();
+                    break Outcome::Rested(current);
+                }
+            }
+        };
+        self.paths[idx] = path;
+
+        outcome
+    }
 
-        let next = position + Direction(1, 1);
-        if !map.contains(&next) {
-            // down right
-            return Some(next);
+    /// drops one grain of sand from the next open source, returning where it came to rest, or
+    /// None once it falls into the abyss or every source is blocked
+    fn step(&mut self) -> Option<Direction> {
+        for _ in 0..self.sources.len() {
+            let idx = self.turn;
+            self.turn = (self.turn + 1) % self.sources.len();
+
+            match self.drop_from(idx) {
+                Outcome::Rested(position) => return Some(position),
+                Outcome::Fell => return None,
+                Outcome::Blocked => continue,
+            }
         }
 
         None
-    };
+    }
+
+    /// drops grains until one fails to come to rest, calling `observe` with the cave and the
+    /// running count each time a grain settles. Returns the number of grains that came to rest.
+    fn run<O>(&mut self, mut observe: O) -> u32
+    where
+        O: FnMut(&Cave, u32),
+    {
+        let mut count = 0;
+        while self.step().is_some() {
+            count += 1;
+            observe(self, count);
+        }
 
-    let mut current_pos = START;
-    while let Some(new_pos) = can_move(current_pos) {
-        current_pos = new_pos;
+        count
     }
-    let is_new = map.insert(current_pos);
 
-    (map, is_new)
+    /// draws the cave as in the puzzle text, cropped to the rock, sand and sources, with the floor
+    /// drawn as rock if there is one
+    fn render(&self) -> String {
+        let bounds = self
+            .map
+            .iter()
+            .filter(|(_, &tile)| tile != Tile::Air)
+            .map(|(position, _)| position + self.offset)
+            .chain(self.sources.iter().copied())
+            .fold(
+                BoundingBox::from_points(self.sources.iter().copied()).unwrap(),
+                BoundingBox::include,
+            );
+        let bounds = if self.has_floor {
+            bounds.include(Direction(bounds.min.0, self.limit))
+        } else {
+            bounds
+        };
+
+        let mut result = String::with_capacity((bounds.width() + 1) * bounds.height());
+        for row in bounds.min.1..=bounds.max.1 {
+            for col in bounds.min.0..=bounds.max.0 {
+                let position = Direction(col, row);
+                result.push(match self.get(position) {
+                    _ if self.has_floor && row == self.limit => '#',
+                    Tile::Rock => '#',
+                    Tile::Sand => 'o',
+                    _ if self.sources.contains(&position) => '+',
+                    _ => '.',
+                });
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
+/// returns the number of units of sand come to rest before sand starts flowing into the abyss below
+pub fn one(file_path: &str) -> u32 {
+    Simulation::default().run(&read_file(file_path)).unwrap()
+}
+
+/// the puzzle rules with a floor 2 below the lowest rock
+fn with_floor() -> Simulation {
+    Simulation {
+        floor: Some(2),
+        ..Default::default()
+    }
 }
 
 /// returns the number of units of sand that come to rest before one stops at 500, 0
 pub fn two(file_path: &str) -> u32 {
-    let mut map: HashSet<Direction> = read_file(file_path).lines().flat_map(parse_line).collect();
-    let max_y = map.iter().fold(0, |acc, next| next.1.max(acc)) + 2;
-    let mut count = 0;
+    with_floor().run(&read_file(file_path)).unwrap()
+}
 
-    let mut flowing = true;
-    while flowing {
-        count += 1;
-        (map, flowing) = step_two(map, max_y);
+/// returns the number of positions sand settles in once the source of a cave with the puzzle's
+/// probes is blocked.
+///
+/// With a floor every grain eventually settles, so sand ends up in exactly the non-rock positions
+/// reachable from the source: those below a sand position or diagonally below one.
+fn fill(cave: &Cave) -> u32 {
+    let width = cave.map.width();
+    let mut previous = vec![false; width];
+    previous[(START - cave.offset).0 as usize] = true;
+    let mut count = 1;
+
+    for row in START.1 + 1..cave.limit {
+        let current: Vec<_> = (0..width)
+            .map(|col| {
+                let is_fed = previous[col.saturating_sub(1)..(col + 2).min(width)]
+                    .iter()
+                    .any(|&is_sand| is_sand);
+                let position = Direction(col as isize, row) + cave.offset;
+                is_fed && cave.get(position) != Tile::Rock
+            })
+            .collect();
+        count += current.iter().filter(|&&is_sand| is_sand).count() as u32;
+        previous = current;
     }
 
-    count - 1
+    count
+}
+
+/// returns the number of units of sand that come to rest before one stops at 500, 0, filling the
+/// cave row by row rather than simulating each grain
+pub fn two_fill(file_path: &str) -> u32 {
+    let cave = with_floor().cave(&read_file(file_path)).unwrap();
+
+    fill(&cave)
+}
+
+/// runs `simulation` on the cave at `file_path`, returning a drawing of the rock and the sand
+/// that came to rest
+pub fn render(file_path: &str, simulation: &Simulation) -> Result<String, String> {
+    let mut cave = simulation.cave(&read_file(file_path))?;
+    cave.run(|_, _| ());
+
+    Ok(cave.render())
+}
+
+/// runs `simulation` on the cave at `file_path`, writing the rendered cave to a numbered text
+/// file in `out_dir` every `every` grains and once more at the end.
+///
+/// Returns the number of units of sand that came to rest.
+pub fn animate(
+    file_path: &str,
+    out_dir: &str,
+    every: u32,
+    simulation: &Simulation,
+) -> Result<u32, String> {
+    if every == 0 {
+        return Err("Frame interval must be at least 1".to_string());
+    }
+    fs::create_dir_all(out_dir)
+        .map_err(|why| format!("Failed to create directory {}: {}", out_dir, why))?;
+
+    let mut cave = simulation.cave(&read_file(file_path))?;
+    let write_frame = |cave: &Cave, count: u32| {
+        let path = Path::new(out_dir).join(format!("frame_{:06}.txt", count));
+        fs::write(&path, cave.render())
+            .map_err(|why| format!("Failed to write {}: {}", path.display(), why))
+    };
+
+    let mut result = write_frame(&cave, 0);
+    let count = cave.run(|cave, count| {
+        if result.is_ok() && count % every == 0 {
+            result = write_frame(cave, count);
+        }
+    });
+    result?;
+    if count % every != 0 {
+        write_frame(&cave, count)?;
+    }
+
+    Ok(count)
 }
 
 #[cfg(test)]
@@ -158,4 +400,139 @@ mod test {
         let actual = two("input/14-t.txt");
         assert_eq!(actual, expected, "{}", msg);
     }
+
+    #[test]
+    fn fill_rows() {
+        let msg = "should match the grain by grain simulation";
+        let expected = two("input/14-t.txt");
+        let actual = two_fill("input/14-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn variants() {
+        let msg = "should run the simulation with the configured settings";
+        let input = read_file("input/14-t.txt");
+        let cases = [
+            (
+                Simulation {
+                    probes: vec![SOUTH, SOUTH_EAST, SOUTH_WEST],
+                    ..Default::default()
+                },
+                Ok(27),
+            ),
+            (
+                Simulation {
+                    sources: vec![START, Direction(497, 0)],
+                    ..Default::default()
+                },
+                Ok(5),
+            ),
+            (
+                Simulation {
+                    sources: vec![START, Direction(497, 0)],
+                    floor: Some(2),
+                    ..Default::default()
+                },
+                Ok(124),
+            ),
+            (
+                Simulation {
+                    floor: Some(1),
+                    ..Default::default()
+                },
+                Ok(79),
+            ),
+            (
+                Simulation {
+                    probes: vec![SOUTH, Direction(1, 0)],
+                    ..Default::default()
+                },
+                Err("Probe Direction(1, 0) does not move downward".to_string()),
+            ),
+            (
+                Simulation {
+                    floor: Some(-1),
+                    ..Default::default()
+                },
+                Err("Floor depth -1 is above the lowest rock".to_string()),
+            ),
+        ];
+        for (simulation, expected) in cases {
+            let actual = simulation.run(&input);
+            assert_eq!(actual, expected, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn render_cave() {
+        let msg = "should draw the rock, sand and source as in the puzzle text";
+        let cave = Simulation::default()
+            .cave(&read_file("input/14-t.txt"))
+            .unwrap();
+        let expected = "\
+......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+........#.
+........#.
+#########.
+";
+        assert_eq!(cave.render(), expected, "{}", msg);
+
+        let expected = "\
+......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.
+";
+        let actual = render("input/14-t.txt", &Simulation::default());
+        assert_eq!(actual, Ok(expected.to_string()), "{}", msg);
+    }
+
+    #[test]
+    fn frames() {
+        let msg = "should write numbered frames, ending with the final cave";
+        let out_dir = std::env::temp_dir().join(format!("advent_2022_14_{}", std::process::id()));
+        let out_dir = out_dir.to_str().unwrap();
+
+        let actual = animate("input/14-t.txt", out_dir, 10, &Simulation::default());
+        assert_eq!(actual, Ok(24), "{}", msg);
+        let mut frames: Vec<_> = fs::read_dir(out_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        frames.sort();
+        let expected = [
+            "frame_000000.txt",
+            "frame_000010.txt",
+            "frame_000020.txt",
+            "frame_000024.txt",
+        ];
+        assert_eq!(frames, expected, "{}", msg);
+        let last = fs::read_to_string(Path::new(out_dir).join("frame_000024.txt")).unwrap();
+        assert_eq!(last.matches('o').count(), 24, "{}", msg);
+
+        let actual = animate("input/14-t.txt", out_dir, 100, &with_floor());
+        assert_eq!(actual, Ok(93), "{}", msg);
+        let last = fs::read_to_string(Path::new(out_dir).join("frame_000093.txt")).unwrap();
+        assert_eq!(last.matches('o').count(), 93, "{}", msg);
+        assert!(
+            last.lines().last().unwrap().chars().all(|ch| ch == '#'),
+            "{}",
+            msg
+        );
+
+        fs::remove_dir_all(out_dir).unwrap();
+    }
 }
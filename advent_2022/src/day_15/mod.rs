@@ -5,7 +5,11 @@ use std::collections::HashSet;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::{day_09::Direction, read_file};
+use crate::{
+    geometry::Direction,
+    interval::{Interval, IntervalSet},
+    read_file,
+};
 
 fn parse_line(line: &str) -> (Direction, Direction) {
     lazy_static! {
@@ -32,50 +36,26 @@ fn parse_line(line: &str) -> (Direction, Direction) {
     (sensor, beacon)
 }
 
-impl Direction {
-    /// Returns the manhattan distance between two points
-    pub fn manhattan(&self, other: &Direction) -> usize {
-        other.0.abs_diff(self.0) + other.1.abs_diff(self.1)
-    }
-}
-
 fn solve_one(readings: &[(Direction, Direction)], row: isize) -> usize {
-    let mut map: HashSet<Direction> = HashSet::new();
-    for (sensor, beacon) in readings {
-        let distance = sensor.manhattan(beacon) as isize;
-
-        if (sensor.1 + distance) < row || (sensor.1 - distance) > row {
-            continue;
-        }
-
-        for y in (0..=distance).rev() {
-            if (sensor.1 + y) != row && (sensor.1 - y) != row {
-                continue;
+    // each sensor covers a span of the row that narrows the further the row is from the sensor
+    let coverage: IntervalSet = readings
+        .iter()
+        .filter_map(|(sensor, beacon)| {
+            let reach = sensor.manhattan(beacon) as isize - (sensor.1 - row).abs();
+            if reach < 0 {
+                None
+            } else {
+                Some(Interval::new(sensor.0 - reach, sensor.0 + reach))
             }
-
-            let width = distance - y;
-            for x in 0..=width {
-                let bottom = sensor.1 + y;
-                let top = sensor.1 - y;
-
-                if top == row {
-                    map.insert(Direction(sensor.0 - x, top));
-                    map.insert(Direction(sensor.0 + x, top));
-                }
-
-                if bottom == row {
-                    map.insert(Direction(sensor.0 - x, bottom));
-                    map.insert(Direction(sensor.0 + x, bottom));
-                }
-            }
-        }
-    }
-    for (sensor, beacon) in readings {
-        map.remove(sensor);
-        map.remove(beacon);
-    }
-
-    map.len()
+        })
+        .collect();
+    let occupied: HashSet<_> = readings
+        .iter()
+        .flat_map(|&(sensor, beacon)| [sensor, beacon])
+        .filter(|&position| position.1 == row && coverage.contains(position.0))
+        .collect();
+
+    coverage.length() - occupied.len()
 }
 
 /// returns the number of positions a beacon cannot be present in a given row
@@ -1,11 +1,11 @@
 //! Solutions to 2020 day 16 problems
 //! --- Day 16: Proboscidea Volcanium ---
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, HashSet};
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::read_file;
+use crate::{read_file, search::all_pairs};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct Valve<'a> {
@@ -25,69 +25,29 @@ fn parse_line(line: &str) -> (&str, Valve) {
         .and_then(|captures| {
             let key = captures.name("key")?.as_str();
             let rate: u32 = captures.name("rate")?.as_str().parse().ok()?;
-            let mut neighbors: HashMap<_, _> = captures
+            let neighbors: HashMap<_, _> = captures
                 .name("valves")?
                 .as_str()
                 .split(", ")
                 .map(|key| (key, 1))
                 .collect();
-            neighbors.insert(key, 0);
 
             Some((key, Valve { rate, neighbors }))
         })
         .unwrap()
 }
 
+/// replaces each valve's tunnels with the travel time to every reachable valve
 fn find_distances<'a>(mut map: HashMap<&'a str, Valve<'a>>) -> HashMap<&'a str, Valve<'a>> {
-    let keys: Vec<_> = map.keys().cloned().collect();
-
-    for key in keys {
-        let mut queue = VecDeque::with_capacity(map.len());
-        let mut visited = HashSet::with_capacity(map.len());
-
-        queue.push_back(key);
-
-        while !queue.is_empty() {
-            let current = queue.pop_front().unwrap();
-            if !visited.insert(current) {
-                continue;
-            }
-
-            let neighbors: Vec<_> = map
-                .get(current)
-                .unwrap()
-                .neighbors
-                .iter()
-                .filter_map(|(&key, val)| if *val == 1 { Some(key) } else { None })
-                .collect();
-            for neighbor in neighbors {
-                if visited.contains(neighbor) {
-                    continue;
-                }
-                queue.push_back(neighbor);
-
-                let distance = map
-                    .get(key)
-                    .map(|valve| {
-                        let prev = valve.neighbors.get(neighbor).unwrap_or(&u32::MAX);
-                        let distance = valve
-                            .neighbors
-                            .get(current)
-                            .map(|d| d + 1)
-                            .unwrap_or(u32::MAX);
-
-                        distance.min(*prev)
-                    })
-                    .unwrap();
-
-                map.entry(key).and_modify(|valve| {
-                    valve.neighbors.insert(neighbor, distance);
-                });
-                map.entry(neighbor).and_modify(|valve| {
-                    valve.neighbors.insert(key, distance);
-                });
-            }
-        }
+    let mut distances = all_pairs(map.keys().copied(), |key| {
+        map[key]
+            .neighbors
+            .iter()
+            .map(|(&neighbor, &distance)| (neighbor, distance))
+    });
+
+    for (key, valve) in map.iter_mut() {
+        valve.neighbors = distances.remove(key).unwrap();
     }
 
     map
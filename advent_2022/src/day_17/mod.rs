@@ -0,0 +1,224 @@
+//! Solutions to 2022 day 17 problems
+//! --- Day 17: Pyroclastic Flow ---
+use std::collections::HashMap;
+
+use crate::read_file;
+
+/// chamber width in units
+const WIDTH: usize = 7;
+
+/// rock shapes as bit rows, bottom row first. The most significant of the 7 bits is the leftmost
+/// column, and each rock is already offset two units from the left wall.
+const ROCKS: [&[u8]; 5] = [
+    // ####
+    &[0b0011110],
+    // .#.
+    // ###
+    // .#.
+    &[0b0001000, 0b0011100, 0b0001000],
+    // ..#
+    // ..#
+    // ###
+    &[0b0011100, 0b0000100, 0b0000100],
+    // #
+    // #
+    // #
+    // #
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    // ##
+    // ##
+    &[0b0011000, 0b0011000],
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Jet {
+    Left,
+    Right,
+}
+
+impl From<char> for Jet {
+    fn from(character: char) -> Self {
+        match character {
+            '<' => Self::Left,
+            '>' => Self::Right,
+            _ => panic!("Invalid character in jet pattern: '{}'", character),
+        }
+    }
+}
+
+fn parse_jets(input: &str) -> Vec<Jet> {
+    input.trim().chars().map(From::from).collect()
+}
+
+/// returns the rock pushed one unit by the jet, or None if a wall is in the way
+fn shift(rock: &[u8], jet: Jet) -> Option<Vec<u8>> {
+    const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+    const RIGHT_WALL: u8 = 1;
+
+    match jet {
+        Jet::Left if rock.iter().all(|row| row & LEFT_WALL == 0) => {
+            Some(rock.iter().map(|row| row << 1).collect())
+        }
+        Jet::Right if rock.iter().all(|row| row & RIGHT_WALL == 0) => {
+            Some(rock.iter().map(|row| row >> 1).collect())
+        }
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Chamber<'a> {
+    rows: Vec<u8>,
+    jets: &'a [Jet],
+    jet_idx: usize,
+    rock_idx: usize,
+}
+
+impl<'a> Chamber<'a> {
+    pub fn new(jets: &'a [Jet]) -> Self {
+        Self {
+            rows: Vec::new(),
+            jets,
+            jet_idx: 0,
+            rock_idx: 0,
+        }
+    }
+
+    /// returns the height of the tower of settled rocks
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// returns true if the rock with its bottom row at `y` overlaps a settled rock
+    fn collides(&self, rock: &[u8], y: usize) -> bool {
+        rock.iter()
+            .enumerate()
+            .any(|(idx, row)| self.rows.get(y + idx).unwrap_or(&0) & row != 0)
+    }
+
+    /// drop the next rock until it comes to rest
+    pub fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.rock_idx].to_vec();
+        let mut y = self.height() + 3;
+
+        loop {
+            let jet = self.jets[self.jet_idx];
+            self.jet_idx = (self.jet_idx + 1) % self.jets.len();
+
+            if let Some(shifted) = shift(&rock, jet) {
+                if !self.collides(&shifted, y) {
+                    rock = shifted;
+                }
+            }
+
+            if y == 0 || self.collides(&rock, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        for (idx, row) in rock.iter().enumerate() {
+            if y + idx >= self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[y + idx] |= row;
+        }
+
+        self.rock_idx = (self.rock_idx + 1) % ROCKS.len();
+    }
+
+    /// returns the depth of the highest settled rock in each column, relative to the top of the
+    /// tower
+    fn skyline(&self) -> [usize; WIDTH] {
+        let mut result = [self.height(); WIDTH];
+
+        for (col, depth) in result.iter_mut().enumerate() {
+            let mask = 1 << (WIDTH - 1 - col);
+            if let Some(found) = self.rows.iter().rev().position(|row| row & mask != 0) {
+                *depth = found;
+            }
+        }
+
+        result
+    }
+}
+
+/// returns the height of the tower after `count` rocks have fallen
+fn tower_height(jets: &[Jet], count: usize) -> usize {
+    let mut chamber = Chamber::new(jets);
+    // (rock idx, jet idx, skyline) -> (rocks dropped, height)
+    let mut seen: HashMap<(usize, usize, [usize; WIDTH]), (usize, usize)> = HashMap::new();
+
+    for dropped in 0..count {
+        let key = (chamber.rock_idx, chamber.jet_idx, chamber.skyline());
+        if let Some((prev_dropped, prev_height)) = seen.insert(key, (dropped, chamber.height())) {
+            // the chamber is in a state we've seen before, so every following cycle adds the
+            // same height
+            let cycle_len = dropped - prev_dropped;
+            let cycle_height = chamber.height() - prev_height;
+            let remaining = count - dropped;
+
+            for _ in 0..(remaining % cycle_len) {
+                chamber.drop_rock();
+            }
+
+            return chamber.height() + (remaining / cycle_len) * cycle_height;
+        }
+
+        chamber.drop_rock();
+    }
+
+    chamber.height()
+}
+
+/// returns the height of the tower after 2022 rocks have stopped falling
+pub fn one(file_path: &str) -> usize {
+    const ROCK_COUNT: usize = 2022;
+    let jets = parse_jets(&read_file(file_path));
+
+    tower_height(&jets, ROCK_COUNT)
+}
+
+/// returns the height of the tower after 1000000000000 rocks have stopped falling
+pub fn two(file_path: &str) -> usize {
+    const ROCK_COUNT: usize = 1_000_000_000_000;
+    let jets = parse_jets(&read_file(file_path));
+
+    tower_height(&jets, ROCK_COUNT)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn drop_rocks() {
+        let msg = "should return the height of the tower after some rocks have fallen";
+        let jets = parse_jets(&read_file("input/17-t.txt"));
+        let cases = [(1, 1), (2, 4), (3, 6), (10, 17)];
+        for (count, expected) in cases {
+            let mut chamber = Chamber::new(&jets);
+            for _ in 0..count {
+                chamber.drop_rock();
+            }
+            assert_eq!(chamber.height(), expected, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the height of the tower after 2022 rocks have stopped falling";
+        let expected = 3068;
+        let actual = one("input/17-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_two() {
+        let msg =
+            "should return the height of the tower after 1000000000000 rocks have stopped falling";
+        let expected = 1514285714288;
+        let actual = two("input/17-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
@@ -0,0 +1,118 @@
+//! Solutions to 2022 day 18 problems
+//! --- Day 18: Boiling Boulders ---
+use std::collections::{HashSet, VecDeque};
+
+use crate::{geometry::Point3, read_file};
+
+fn parse_line(line: &str) -> Result<Point3, String> {
+    let parts = line
+        .split(',')
+        .map(|num| {
+            num.trim()
+                .parse()
+                .map_err(|_| format!("Failed to parse number: '{}'", num))
+        })
+        .collect::<Result<Vec<isize>, _>>()?;
+
+    match parts[..] {
+        [x, y, z] => Ok(Point3(x, y, z)),
+        _ => Err(format!("Invalid cube position: '{}'", line)),
+    }
+}
+
+fn parse_cubes(input: &str) -> HashSet<Point3> {
+    input
+        .lines()
+        .map(parse_line)
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+/// returns the surface area of every exposed cube face
+fn surface_area(cubes: &HashSet<Point3>) -> usize {
+    cubes
+        .iter()
+        .flat_map(|cube| cube.neighbors())
+        .filter(|neighbor| !cubes.contains(neighbor))
+        .count()
+}
+
+/// returns the surface area reachable from outside the droplet
+fn exterior_area(cubes: &HashSet<Point3>) -> usize {
+    let (min, max) = match cubes.iter().next() {
+        Some(&first) => cubes.iter().fold((first, first), |(min, max), &cube| {
+            (min.min(cube), max.max(cube))
+        }),
+        None => return 0,
+    };
+    // leave a layer of air around the droplet so the fill can flow around it
+    let min = min - Point3(1, 1, 1);
+    let max = max + Point3(1, 1, 1);
+    let in_bounds = |point: &Point3| {
+        (min.0..=max.0).contains(&point.0)
+            && (min.1..=max.1).contains(&point.1)
+            && (min.2..=max.2).contains(&point.2)
+    };
+
+    let mut area = 0;
+    let mut visited: HashSet<Point3> = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    queue.push_back(min);
+    visited.insert(min);
+
+    while let Some(current) = queue.pop_front() {
+        for neighbor in current.neighbors().filter(in_bounds) {
+            if cubes.contains(&neighbor) {
+                area += 1;
+            } else if visited.insert(neighbor) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    area
+}
+
+/// returns the surface area of the scanned lava droplet
+pub fn one(file_path: &str) -> usize {
+    let cubes = parse_cubes(&read_file(file_path));
+
+    surface_area(&cubes)
+}
+
+/// returns the exterior surface area of the scanned lava droplet
+pub fn two(file_path: &str) -> usize {
+    let cubes = parse_cubes(&read_file(file_path));
+
+    exterior_area(&cubes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn small_droplet() {
+        let msg = "should return the surface area of two adjacent cubes";
+        let cubes = parse_cubes("1,1,1\n2,1,1");
+        assert_eq!(surface_area(&cubes), 10, "{}", msg);
+        assert_eq!(exterior_area(&cubes), 10, "{}", msg);
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the surface area of the scanned lava droplet";
+        let expected = 64;
+        let actual = one("input/18-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_two() {
+        let msg = "should return the exterior surface area of the scanned lava droplet";
+        let expected = 58;
+        let actual = two("input/18-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
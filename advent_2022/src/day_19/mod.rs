@@ -0,0 +1,215 @@
+//! Solutions to 2022 day 19 problems
+//! --- Day 19: Not Enough Minerals ---
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::read_file;
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+/// quantity of each resource (or robot), indexed by ore, clay, obsidian, geode
+type Resources = [u32; 4];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Blueprint {
+    id: u32,
+    /// cost of each robot type, indexed by the resource the robot collects
+    costs: [Resources; 4],
+}
+
+fn parse_blueprints(input: &str) -> Vec<Blueprint> {
+    lazy_static! {
+        static ref BLUEPRINT_RE: Regex = Regex::new(concat!(
+            r#"Blueprint (?P<id>\d+):\s+"#,
+            r#"Each ore robot costs (?P<ore>\d+) ore.\s+"#,
+            r#"Each clay robot costs (?P<clay>\d+) ore.\s+"#,
+            r#"Each obsidian robot costs (?P<obsidian_ore>\d+) ore and (?P<obsidian_clay>\d+) clay.\s+"#,
+            r#"Each geode robot costs (?P<geode_ore>\d+) ore and (?P<geode_obsidian>\d+) obsidian."#,
+        ))
+        .unwrap();
+    }
+
+    BLUEPRINT_RE
+        .captures_iter(input)
+        .map(|captures| {
+            let get = |name| -> u32 {
+                captures
+                    .name(name)
+                    .and_then(|value| value.as_str().parse().ok())
+                    .unwrap_or_else(|| panic!("Failed to parse blueprint {}", name))
+            };
+
+            Blueprint {
+                id: get("id"),
+                costs: [
+                    [get("ore"), 0, 0, 0],
+                    [get("clay"), 0, 0, 0],
+                    [get("obsidian_ore"), get("obsidian_clay"), 0, 0],
+                    [get("geode_ore"), 0, get("geode_obsidian"), 0],
+                ],
+            }
+        })
+        .collect()
+}
+
+/// returns the number of minutes until `robots` have gathered enough for `cost`, or None if a
+/// required resource isn't being collected
+fn wait_time(cost: &Resources, amounts: &Resources, robots: &Resources) -> Option<u32> {
+    cost.iter()
+        .zip(amounts)
+        .zip(robots)
+        .try_fold(0, |wait, ((&cost, &amount), &robots)| {
+            if cost <= amount {
+                Some(wait)
+            } else if robots == 0 {
+                None
+            } else {
+                Some(wait.max((cost - amount).div_ceil(robots)))
+            }
+        })
+}
+
+/// depth first search over the next robot to build
+fn search(
+    blueprint: &Blueprint,
+    max_robots: &Resources,
+    robots: Resources,
+    amounts: Resources,
+    minutes: u32,
+    best: &mut u32,
+) {
+    let idle_geodes = amounts[GEODE] + robots[GEODE] * minutes;
+    *best = (*best).max(idle_geodes);
+
+    // even building a geode robot every remaining minute can't beat the best so far
+    if idle_geodes + minutes * minutes.saturating_sub(1) / 2 <= *best {
+        return;
+    }
+
+    for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+        if robots[robot] >= max_robots[robot] {
+            // can't spend more than this per minute anyway
+            continue;
+        }
+
+        let cost = &blueprint.costs[robot];
+        let wait = match wait_time(cost, &amounts, &robots) {
+            // a robot finished in the last minute can't collect anything
+            Some(wait) if wait + 1 < minutes => wait,
+            _ => continue,
+        };
+
+        let elapsed = wait + 1;
+        let mut next_amounts = amounts;
+        for idx in 0..next_amounts.len() {
+            next_amounts[idx] += robots[idx] * elapsed;
+            next_amounts[idx] -= cost[idx];
+        }
+        let mut next_robots = robots;
+        next_robots[robot] += 1;
+
+        search(
+            blueprint,
+            max_robots,
+            next_robots,
+            next_amounts,
+            minutes - elapsed,
+            best,
+        );
+    }
+}
+
+/// returns the largest number of geodes that can be opened in `minutes`
+fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let mut max_robots = [u32::MAX; 4];
+    for resource in [ORE, CLAY, OBSIDIAN] {
+        max_robots[resource] = blueprint
+            .costs
+            .iter()
+            .map(|cost| cost[resource])
+            .max()
+            .unwrap_or_default();
+    }
+
+    let mut best = 0;
+    search(
+        blueprint,
+        &max_robots,
+        [1, 0, 0, 0],
+        [0; 4],
+        minutes,
+        &mut best,
+    );
+
+    best
+}
+
+/// returns the sum of the quality levels of all blueprints
+pub fn one(file_path: &str) -> u32 {
+    const MINUTES: u32 = 24;
+
+    parse_blueprints(&read_file(file_path))
+        .iter()
+        .map(|blueprint| blueprint.id * max_geodes(blueprint, MINUTES))
+        .sum()
+}
+
+/// returns the product of the max geodes opened by the first three blueprints
+pub fn two(file_path: &str) -> u32 {
+    const MINUTES: u32 = 32;
+
+    parse_blueprints(&read_file(file_path))
+        .iter()
+        .take(3)
+        .map(|blueprint| max_geodes(blueprint, MINUTES))
+        .product()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let msg = "should parse blueprints split across lines";
+        let input = "Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.";
+        let expected = vec![Blueprint {
+            id: 1,
+            costs: [[4, 0, 0, 0], [2, 0, 0, 0], [3, 14, 0, 0], [2, 0, 7, 0]],
+        }];
+        let actual = parse_blueprints(input);
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn geodes() {
+        let msg = "should return the largest number of geodes that can be opened";
+        let blueprints = parse_blueprints(&read_file("input/19-t.txt"));
+        assert_eq!(max_geodes(&blueprints[0], 24), 9, "{}", msg);
+        assert_eq!(max_geodes(&blueprints[1], 24), 12, "{}", msg);
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the sum of the quality levels of all blueprints";
+        let expected = 33;
+        let actual = one("input/19-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_two() {
+        let msg =
+            "should return the product of the max geodes opened by the first three blueprints";
+        let expected = 56 * 62;
+        let actual = two("input/19-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
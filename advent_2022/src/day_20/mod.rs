@@ -0,0 +1,119 @@
+//! Solutions to 2022 day 20 problems
+//! --- Day 20: Grove Positioning System ---
+use crate::read_file;
+
+fn parse_numbers(input: &str) -> Vec<i64> {
+    input
+        .lines()
+        .map(|line| {
+            line.trim()
+                .parse()
+                .unwrap_or_else(|_| panic!("Failed to parse number: {}", line))
+        })
+        .collect()
+}
+
+/// mix the numbers `rounds` times, returning the resulting order.
+///
+/// Numbers may repeat, so the list is tracked by the original index of each number rather than
+/// by value.
+fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let mut order: Vec<usize> = (0..numbers.len()).collect();
+    // a number moving around the circle skips over itself, so positions wrap at len - 1
+    let wrap = numbers.len() as i64 - 1;
+
+    if wrap < 1 {
+        return numbers.to_vec();
+    }
+
+    for _ in 0..rounds {
+        for (original, value) in numbers.iter().enumerate() {
+            let position = order.iter().position(|&idx| idx == original).unwrap();
+            order.remove(position);
+
+            let destination = (position as i64 + value).rem_euclid(wrap) as usize;
+            order.insert(destination, original);
+        }
+    }
+
+    order.into_iter().map(|idx| numbers[idx]).collect()
+}
+
+/// returns the sum of the 1000th, 2000th and 3000th numbers after the value 0
+fn grove_coordinates(mixed: &[i64]) -> i64 {
+    let zero = mixed
+        .iter()
+        .position(|&value| value == 0)
+        .expect("File contains no 0 value");
+
+    [1000, 2000, 3000]
+        .iter()
+        .map(|offset| mixed[(zero + offset) % mixed.len()])
+        .sum()
+}
+
+/// returns the sum of the grove coordinates after mixing
+pub fn one(file_path: &str) -> i64 {
+    let numbers = parse_numbers(&read_file(file_path));
+
+    grove_coordinates(&mix(&numbers, 1))
+}
+
+/// returns the sum of the grove coordinates after decrypting and mixing 10 times
+pub fn two(file_path: &str) -> i64 {
+    const DECRYPTION_KEY: i64 = 811589153;
+    const ROUNDS: usize = 10;
+    let numbers: Vec<_> = parse_numbers(&read_file(file_path))
+        .iter()
+        .map(|value| value * DECRYPTION_KEY)
+        .collect();
+
+    grove_coordinates(&mix(&numbers, ROUNDS))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// rotate the circular list so that it begins with `first`
+    fn rotate_to(mut list: Vec<i64>, first: i64) -> Vec<i64> {
+        let idx = list.iter().position(|&value| value == first).unwrap();
+        list.rotate_left(idx);
+        list
+    }
+
+    #[test]
+    fn mix_once() {
+        let msg = "should move each number forward or back by its value";
+        let numbers = parse_numbers(&read_file("input/20-t.txt"));
+        let expected = vec![1, 2, -3, 4, 0, 3, -2];
+        let actual = rotate_to(mix(&numbers, 1), 1);
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn mix_duplicates() {
+        let msg = "should move duplicate values independently";
+        let numbers = vec![0, 1, 1, 5];
+        let expected = vec![0, 1, 5, 1];
+        let actual = rotate_to(mix(&numbers, 1), 0);
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the sum of the grove coordinates after mixing";
+        let expected = 3;
+        let actual = one("input/20-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_two() {
+        let msg =
+            "should return the sum of the grove coordinates after decrypting and mixing 10 times";
+        let expected = 1623178306;
+        let actual = two("input/20-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
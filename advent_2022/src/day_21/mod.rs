@@ -0,0 +1,211 @@
+//! Solutions to 2022 day 21 problems
+//! --- Day 21: Monkey Math ---
+use std::collections::HashMap;
+
+use crate::read_file;
+
+const ROOT: &str = "root";
+const HUMAN: &str = "humn";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operation {
+    pub fn apply(self, left: i64, right: i64) -> i64 {
+        match self {
+            Self::Add => left + right,
+            Self::Sub => left - right,
+            Self::Mul => left * right,
+            Self::Div => left / right,
+        }
+    }
+}
+
+impl std::str::FromStr for Operation {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "+" => Ok(Self::Add),
+            "-" => Ok(Self::Sub),
+            "*" => Ok(Self::Mul),
+            "/" => Ok(Self::Div),
+            _ => Err(format!("Invalid operation: '{}'", value)),
+        }
+    }
+}
+
+/// a single monkey's job, as written in the input
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Job<'a> {
+    Number(i64),
+    Math(&'a str, Operation, &'a str),
+}
+
+fn parse_line(line: &str) -> Result<(&str, Job<'_>), String> {
+    let (name, job) = line
+        .split_once(": ")
+        .ok_or_else(|| format!("Invalid monkey job: '{}'", line))?;
+
+    let parts: Vec<_> = job.split_whitespace().collect();
+    let job = match parts[..] {
+        [number] => Job::Number(
+            number
+                .parse()
+                .map_err(|_| format!("Failed to parse number: '{}'", number))?,
+        ),
+        [left, op, right] => Job::Math(left, op.parse()?, right),
+        _ => return Err(format!("Invalid monkey job: '{}'", line)),
+    };
+
+    Ok((name, job))
+}
+
+fn parse_jobs(input: &str) -> HashMap<&str, Job<'_>> {
+    input
+        .lines()
+        .map(parse_line)
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+/// expression tree built from the monkey jobs
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Expr {
+    Number(i64),
+    /// the value we need to solve for
+    Unknown,
+    Math(Box<Expr>, Operation, Box<Expr>),
+}
+
+/// builds the expression tree rooted at `name`, folding every subtree that doesn't depend on
+/// `unknown` into a single number
+fn build(jobs: &HashMap<&str, Job<'_>>, name: &str, unknown: Option<&str>) -> Expr {
+    if Some(name) == unknown {
+        return Expr::Unknown;
+    }
+
+    match jobs.get(name) {
+        Some(&Job::Number(value)) => Expr::Number(value),
+        Some(&Job::Math(left, op, right)) => {
+            match (build(jobs, left, unknown), build(jobs, right, unknown)) {
+                (Expr::Number(left), Expr::Number(right)) => Expr::Number(op.apply(left, right)),
+                (left, right) => Expr::Math(Box::new(left), op, Box::new(right)),
+            }
+        }
+        None => panic!("Unknown monkey: '{}'", name),
+    }
+}
+
+/// returns the value of the unknown that makes `expr` equal `target`.
+///
+/// Works back down the tree from the root, inverting each operation along the path to the
+/// unknown leaf. Every other subtree has already been folded into a number by [`build`].
+fn solve(expr: &Expr, target: i64) -> Result<i64, String> {
+    match expr {
+        Expr::Unknown => Ok(target),
+        Expr::Number(_) => Err("Expression does not contain the unknown".to_string()),
+        Expr::Math(left, op, right) => match (left.as_ref(), right.as_ref()) {
+            // unknown on the left: x op value = target
+            (unknown, &Expr::Number(value)) => {
+                let target = match op {
+                    Operation::Add => target - value,
+                    Operation::Sub => target + value,
+                    Operation::Mul => target / value,
+                    Operation::Div => target * value,
+                };
+                solve(unknown, target)
+            }
+            // unknown on the right: value op x = target
+            (&Expr::Number(value), unknown) => {
+                let target = match op {
+                    Operation::Add => target - value,
+                    Operation::Sub => value - target,
+                    Operation::Mul => target / value,
+                    Operation::Div => value / target,
+                };
+                solve(unknown, target)
+            }
+            _ => Err("Unknown appears on both sides of an operation".to_string()),
+        },
+    }
+}
+
+/// returns the number yelled by the monkey named root
+pub fn one(file_path: &str) -> i64 {
+    let input = read_file(file_path);
+    let jobs = parse_jobs(&input);
+
+    match build(&jobs, ROOT, None) {
+        Expr::Number(value) => value,
+        expr => panic!("Failed to evaluate root: {:?}", expr),
+    }
+}
+
+/// returns the number to yell so that both sides of root's equality test match
+pub fn two(file_path: &str) -> i64 {
+    let input = read_file(file_path);
+    let jobs = parse_jobs(&input);
+
+    match build(&jobs, ROOT, Some(HUMAN)) {
+        Expr::Math(left, _, right) => match (*left, *right) {
+            (Expr::Number(value), expr) | (expr, Expr::Number(value)) => solve(&expr, value),
+            _ => Err("Unknown appears on both sides of root".to_string()),
+        },
+        expr => Err(format!("Invalid root expression: {:?}", expr)),
+    }
+    .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solve_expr() {
+        let msg = "should return the value of the unknown that satisfies the expression";
+        // (4 + 2 * (x - 3)) / 4 = 150
+        let expr = Expr::Math(
+            Box::new(Expr::Math(
+                Box::new(Expr::Number(4)),
+                Operation::Add,
+                Box::new(Expr::Math(
+                    Box::new(Expr::Number(2)),
+                    Operation::Mul,
+                    Box::new(Expr::Math(
+                        Box::new(Expr::Unknown),
+                        Operation::Sub,
+                        Box::new(Expr::Number(3)),
+                    )),
+                )),
+            )),
+            Operation::Div,
+            Box::new(Expr::Number(4)),
+        );
+        let expected = Ok(301);
+        let actual = solve(&expr, 150);
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the number yelled by the monkey named root";
+        let expected = 152;
+        let actual = one("input/21-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_two() {
+        let msg =
+            "should return the number to yell so that both sides of root's equality test match";
+        let expected = 301;
+        let actual = two("input/21-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
@@ -0,0 +1,442 @@
+//! Solutions to 2022 day 22 problems
+//! --- Day 22: Monkey Map ---
+use std::collections::HashMap;
+
+use crate::{
+    geometry::{Direction, Point3, EAST, NORTH, SOUTH, WEST},
+    parse_terrain, read_file,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Tile {
+    /// off the edge of the map
+    Void,
+    Open,
+    Wall,
+}
+
+impl From<char> for Tile {
+    fn from(character: char) -> Self {
+        match character {
+            ' ' => Self::Void,
+            '.' => Self::Open,
+            '#' => Self::Wall,
+            _ => panic!("Invalid character in map data: '{}'", character),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Step {
+    Walk(usize),
+    Left,
+    Right,
+}
+
+fn parse_path(input: &str) -> Result<Vec<Step>, String> {
+    let mut result = Vec::new();
+    let mut count = String::new();
+    let flush = |count: &mut String, result: &mut Vec<Step>| -> Result<(), String> {
+        if !count.is_empty() {
+            let value = count
+                .parse()
+                .map_err(|_| format!("Failed to parse number: '{}'", count))?;
+            result.push(Step::Walk(value));
+            count.clear();
+        }
+        Ok(())
+    };
+
+    for ch in input.trim().chars() {
+        match ch {
+            '0'..='9' => count.push(ch),
+            'L' | 'R' => {
+                flush(&mut count, &mut result)?;
+                result.push(if ch == 'L' { Step::Left } else { Step::Right });
+            }
+            _ => return Err(format!("Invalid character in path: '{}'", ch)),
+        }
+    }
+    flush(&mut count, &mut result)?;
+
+    Ok(result)
+}
+
+/// facing, valued as in the final password
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Facing {
+    Right = 0,
+    Down,
+    Left,
+    Up,
+}
+
+impl Facing {
+    const ALL: [Facing; 4] = [Self::Right, Self::Down, Self::Left, Self::Up];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % Self::ALL.len()]
+    }
+
+    /// returns the grid step for this facing
+    pub fn direction(self) -> Direction {
+        match self {
+            Self::Right => EAST,
+            Self::Down => SOUTH,
+            Self::Left => WEST,
+            Self::Up => NORTH,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Board {
+    tiles: Vec<Vec<Tile>>,
+}
+
+impl Board {
+    /// returns the tile at `position`, treating anything outside the map as [`Tile::Void`]
+    pub fn get(&self, position: Direction) -> Tile {
+        if position.0 < 0 || position.1 < 0 {
+            return Tile::Void;
+        }
+
+        self.tiles
+            .get(position.1 as usize)
+            .and_then(|row| row.get(position.0 as usize))
+            .copied()
+            .unwrap_or(Tile::Void)
+    }
+
+    /// returns the leftmost open tile of the top row
+    pub fn start(&self) -> Direction {
+        self.tiles[0]
+            .iter()
+            .position(|&tile| tile == Tile::Open)
+            .map(|col| Direction(col as isize, 0))
+            .expect("Top row contains no open tiles")
+    }
+
+    /// returns the number of tiles on the map
+    pub fn area(&self) -> usize {
+        self.tiles
+            .iter()
+            .flatten()
+            .filter(|&&tile| tile != Tile::Void)
+            .count()
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Board, Vec<Step>), String> {
+    let (map, path) = input
+        .split_once("\n\n")
+        .ok_or("Failed to parse input file.")?;
+    let board = Board {
+        tiles: parse_terrain(map),
+    };
+
+    Ok((board, parse_path(path)?))
+}
+
+/// returns the final password after following the path, using `wrap` to find the position and
+/// facing whenever a step would leave the map
+fn walk<F>(board: &Board, path: &[Step], wrap: F) -> usize
+where
+    F: Fn(Direction, Facing) -> (Direction, Facing),
+{
+    let mut position = board.start();
+    let mut facing = Facing::Right;
+
+    for step in path {
+        match step {
+            Step::Left => facing = facing.turn_left(),
+            Step::Right => facing = facing.turn_right(),
+            Step::Walk(count) => {
+                for _ in 0..*count {
+                    let mut next = (position + facing.direction(), facing);
+                    if board.get(next.0) == Tile::Void {
+                        next = wrap(position, facing);
+                    }
+                    if board.get(next.0) == Tile::Wall {
+                        break;
+                    }
+                    (position, facing) = next;
+                }
+            }
+        }
+    }
+
+    1000 * (position.1 as usize + 1) + 4 * (position.0 as usize + 1) + facing as usize
+}
+
+/// wraps around to the opposite side of the current row or column
+fn wrap_flat(board: &Board) -> impl Fn(Direction, Facing) -> (Direction, Facing) + '_ {
+    move |position, facing| {
+        let back = -facing.direction();
+        let mut current = position;
+        while board.get(current + back) != Tile::Void {
+            current = current + back;
+        }
+
+        (current, facing)
+    }
+}
+
+/// orientation of a cube face, as the 3d unit vectors for the map's right and down directions
+/// and the face's outward normal
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Frame {
+    right: Point3,
+    down: Point3,
+    normal: Point3,
+}
+
+impl Frame {
+    /// returns the frame of the face adjacent in the `facing` direction once the net is folded
+    pub fn fold(self, facing: Facing) -> Self {
+        let Frame {
+            right,
+            down,
+            normal,
+        } = self;
+
+        match facing {
+            Facing::Right => Frame {
+                right: -normal,
+                down,
+                normal: right,
+            },
+            Facing::Left => Frame {
+                right: normal,
+                down,
+                normal: -right,
+            },
+            Facing::Down => Frame {
+                right,
+                down: -normal,
+                normal: down,
+            },
+            Facing::Up => Frame {
+                right,
+                down: normal,
+                normal: -down,
+            },
+        }
+    }
+
+    /// returns the 3d direction of travel for `facing` on this face
+    pub fn heading(self, facing: Facing) -> Point3 {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => -self.right,
+            Facing::Up => -self.down,
+        }
+    }
+
+    /// returns the facing that travels in the 3d `heading` on this face
+    pub fn facing(self, heading: Point3) -> Facing {
+        Facing::ALL
+            .into_iter()
+            .find(|&facing| self.heading(facing) == heading)
+            .expect("Heading is not parallel to this face")
+    }
+
+    /// returns the 3d position of the tile at `col`, `row` within the face.
+    ///
+    /// Coordinates are doubled so every tile center is an integer: the cube spans -size..=size on
+    /// each axis.
+    pub fn point(self, size: isize, col: isize, row: isize) -> Point3 {
+        self.normal * size + self.right * (2 * col + 1 - size) + self.down * (2 * row + 1 - size)
+    }
+}
+
+/// the board folded into a cube
+#[derive(Clone, Debug)]
+struct Cube {
+    /// face edge length
+    size: isize,
+    /// face orientations, keyed by the face's position in the net
+    faces: HashMap<Direction, Frame>,
+    /// map positions, keyed by 3d position
+    tiles: HashMap<Point3, Direction>,
+}
+
+impl Cube {
+    pub fn new(board: &Board) -> Result<Self, String> {
+        let face_area = board.area() / 6;
+        let size = (face_area as f64).sqrt() as isize;
+        if (size * size) as usize != face_area || face_area * 6 != board.area() {
+            return Err("Map area does not fit a cube".to_string());
+        }
+
+        // walk the net, folding each face relative to its already placed neighbor
+        let first = Direction(board.start().0 / size, 0);
+        let mut faces = HashMap::new();
+        let mut stack = vec![first];
+        faces.insert(
+            first,
+            Frame {
+                right: Point3(1, 0, 0),
+                down: Point3(0, 1, 0),
+                normal: Point3(0, 0, -1),
+            },
+        );
+
+        while let Some(face) = stack.pop() {
+            let frame = faces[&face];
+            for facing in Facing::ALL {
+                let next = face + facing.direction();
+                let corner = Direction(next.0 * size, next.1 * size);
+                if board.get(corner) == Tile::Void || faces.contains_key(&next) {
+                    continue;
+                }
+
+                faces.insert(next, frame.fold(facing));
+                stack.push(next);
+            }
+        }
+
+        if faces.len() != 6 {
+            return Err(format!("Map folds into {} faces", faces.len()));
+        }
+
+        let mut tiles = HashMap::new();
+        for (face, frame) in &faces {
+            for row in 0..size {
+                for col in 0..size {
+                    let position = Direction(face.0 * size + col, face.1 * size + row);
+                    tiles.insert(frame.point(size, col, row), position);
+                }
+            }
+        }
+
+        Ok(Self { size, faces, tiles })
+    }
+
+    fn frame(&self, position: Direction) -> Frame {
+        let face = Direction(
+            position.0.div_euclid(self.size),
+            position.1.div_euclid(self.size),
+        );
+        self.faces[&face]
+    }
+
+    fn point(&self, position: Direction) -> Point3 {
+        self.frame(position).point(
+            self.size,
+            position.0.rem_euclid(self.size),
+            position.1.rem_euclid(self.size),
+        )
+    }
+
+    /// wraps over the cube edge onto the adjoining face
+    pub fn wrap(&self, position: Direction, facing: Facing) -> (Direction, Facing) {
+        let from = self.frame(position);
+        // step off the edge of this face and down onto the next one
+        let target = self.point(position) + from.heading(facing) - from.normal;
+        let next = self.tiles[&target];
+
+        (next, self.frame(next).facing(-from.normal))
+    }
+}
+
+/// returns the final password
+pub fn one(file_path: &str) -> usize {
+    let (board, path) = parse_input(&read_file(file_path)).unwrap();
+
+    walk(&board, &path, wrap_flat(&board))
+}
+
+/// returns the final password when the map is folded into a cube
+pub fn two(file_path: &str) -> usize {
+    let (board, path) = parse_input(&read_file(file_path)).unwrap();
+    let cube = Cube::new(&board).unwrap();
+
+    walk(&board, &path, |position, facing| {
+        cube.wrap(position, facing)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path() {
+        let msg = "should parse a path description";
+        let expected = Ok(vec![
+            Step::Walk(10),
+            Step::Right,
+            Step::Walk(5),
+            Step::Left,
+            Step::Walk(15),
+        ]);
+        let actual = parse_path("10R5L15\n");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn cube_wrap() {
+        let msg = "should wrap onto the adjoining cube face";
+        let (board, _) = parse_input(&read_file("input/22-t.txt")).unwrap();
+        let cube = Cube::new(&board).unwrap();
+        let cases = [
+            (
+                (Direction(11, 5), Facing::Right),
+                (Direction(14, 8), Facing::Down),
+            ),
+            (
+                (Direction(10, 11), Facing::Down),
+                (Direction(1, 7), Facing::Up),
+            ),
+            (
+                (Direction(6, 4), Facing::Up),
+                (Direction(8, 2), Facing::Right),
+            ),
+        ];
+        for ((position, facing), expected) in cases {
+            let actual = cube.wrap(position, facing);
+            assert_eq!(actual, expected, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn cube_wrap_reversible() {
+        let msg = "should return to the starting tile when wrapping back over the same edge";
+        let (board, _) = parse_input(&read_file("input/22-t.txt")).unwrap();
+        let cube = Cube::new(&board).unwrap();
+        for &position in cube.tiles.values() {
+            for facing in Facing::ALL {
+                if board.get(position + facing.direction()) != Tile::Void {
+                    continue;
+                }
+                let (next, next_facing) = cube.wrap(position, facing);
+                let reverse = next_facing.turn_right().turn_right();
+                let (actual, actual_facing) = cube.wrap(next, reverse);
+                assert_eq!(actual, position, "{}", msg);
+                assert_eq!(actual_facing, facing.turn_right().turn_right(), "{}", msg);
+            }
+        }
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the final password";
+        let expected = 6032;
+        let actual = one("input/22-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_two() {
+        let msg = "should return the final password when the map is folded into a cube";
+        let expected = 5031;
+        let actual = two("input/22-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
@@ -0,0 +1,140 @@
+//! Solutions to 2022 day 23 problems
+//! --- Day 23: Unstable Diffusion ---
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    geometry::{
+        BoundingBox, Direction, ADJACENT, EAST, NORTH, NORTH_EAST, NORTH_WEST, SOUTH, SOUTH_EAST,
+        SOUTH_WEST, WEST,
+    },
+    read_file,
+};
+
+/// proposed move direction and the positions that must be empty to propose it, in initial
+/// priority order
+const PROPOSALS: [(Direction, [Direction; 3]); 4] = [
+    (NORTH, [NORTH, NORTH_EAST, NORTH_WEST]),
+    (SOUTH, [SOUTH, SOUTH_EAST, SOUTH_WEST]),
+    (WEST, [WEST, NORTH_WEST, SOUTH_WEST]),
+    (EAST, [EAST, NORTH_EAST, SOUTH_EAST]),
+];
+
+fn parse_elves(input: &str) -> HashSet<Direction> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, ch)| ch == '#')
+                .map(move |(col, _)| Direction(col as isize, row as isize))
+        })
+        .collect()
+}
+
+/// returns the position the elf proposes to move to, if any
+fn propose(elves: &HashSet<Direction>, elf: Direction, round: usize) -> Option<Direction> {
+    if ADJACENT
+        .iter()
+        .all(|&neighbor| !elves.contains(&(elf + neighbor)))
+    {
+        return None;
+    }
+
+    PROPOSALS
+        .iter()
+        .cycle()
+        .skip(round % PROPOSALS.len())
+        .take(PROPOSALS.len())
+        .find(|(_, checks)| checks.iter().all(|&check| !elves.contains(&(elf + check))))
+        .map(|&(step, _)| elf + step)
+}
+
+/// runs a single round, returning the new positions and whether any elf moved
+fn play_round(elves: HashSet<Direction>, round: usize) -> (HashSet<Direction>, bool) {
+    let proposals: Vec<_> = elves
+        .iter()
+        .map(|&elf| (elf, propose(&elves, elf, round)))
+        .collect();
+    let mut counts: HashMap<Direction, usize> = HashMap::new();
+    for target in proposals.iter().filter_map(|(_, target)| *target) {
+        *counts.entry(target).or_insert(0) += 1;
+    }
+
+    let mut moved = false;
+    let result = proposals
+        .into_iter()
+        .map(|(elf, target)| match target {
+            Some(target) if counts[&target] == 1 => {
+                moved = true;
+                target
+            }
+            _ => elf,
+        })
+        .collect();
+
+    (result, moved)
+}
+
+/// returns the number of empty tiles in the smallest rectangle containing every elf
+fn empty_tiles(elves: &HashSet<Direction>) -> usize {
+    BoundingBox::from_points(elves.iter().copied()).map_or(0, |bounds| bounds.area()) - elves.len()
+}
+
+/// returns the number of empty ground tiles in the elves' bounding rectangle after 10 rounds
+pub fn one(file_path: &str) -> usize {
+    const ROUNDS: usize = 10;
+    let mut elves = parse_elves(&read_file(file_path));
+
+    for round in 0..ROUNDS {
+        (elves, _) = play_round(elves, round);
+    }
+
+    empty_tiles(&elves)
+}
+
+/// returns the number of the first round where no elf moves
+pub fn two(file_path: &str) -> usize {
+    let mut elves = parse_elves(&read_file(file_path));
+    let mut moved = true;
+    let mut round = 0;
+
+    while moved {
+        (elves, moved) = play_round(elves, round);
+        round += 1;
+    }
+
+    round
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn small_example() {
+        let msg = "should spread the elves according to the proposal rules";
+        let mut elves = parse_elves(".....\n..##.\n..#..\n.....\n..##.\n.....");
+        for round in 0..3 {
+            (elves, _) = play_round(elves, round);
+        }
+        let expected = parse_elves("..#..\n....#\n#....\n....#\n.....\n..#..");
+        assert_eq!(elves, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the number of empty ground tiles in the elves' bounding rectangle after 10 rounds";
+        let expected = 110;
+        let actual = one("input/23-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_two() {
+        let msg = "should return the number of the first round where no elf moves";
+        let expected = 20;
+        let actual = two("input/23-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
@@ -0,0 +1,181 @@
+//! Solutions to 2022 day 24 problems
+//! --- Day 24: Blizzard Basin ---
+use crate::{
+    geometry::{Direction, EAST, NORTH, SOUTH, WEST, ZERO},
+    read_file,
+    search::astar,
+};
+
+/// moves available each minute, including waiting in place
+const MOVES: [Direction; 5] = [ZERO, NORTH, SOUTH, WEST, EAST];
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+/// the valley interior, excluding the surrounding walls
+#[derive(Clone, Debug)]
+struct Valley {
+    width: isize,
+    height: isize,
+    /// initial blizzard positions
+    tiles: Vec<Vec<char>>,
+}
+
+impl Valley {
+    /// the entrance, just above the top left of the interior
+    pub fn start(&self) -> Direction {
+        Direction(0, -1)
+    }
+
+    /// the exit, just below the bottom right of the interior
+    pub fn goal(&self) -> Direction {
+        Direction(self.width - 1, self.height)
+    }
+
+    /// number of minutes before the blizzards return to their initial positions
+    pub fn period(&self) -> usize {
+        lcm(self.width as usize, self.height as usize)
+    }
+
+    fn initial(&self, col: isize, row: isize) -> char {
+        self.tiles[row.rem_euclid(self.height) as usize][col.rem_euclid(self.width) as usize]
+    }
+
+    /// returns true if `position` is free of walls and blizzards at `time`
+    pub fn is_clear(&self, position: Direction, time: usize) -> bool {
+        if position == self.start() || position == self.goal() {
+            return true;
+        }
+
+        let Direction(col, row) = position;
+        if col < 0 || row < 0 || col >= self.width || row >= self.height {
+            return false;
+        }
+
+        // look back along each axis for a blizzard that would arrive here now
+        let time = time as isize;
+        self.initial(col - time, row) != '>'
+            && self.initial(col + time, row) != '<'
+            && self.initial(col, row - time) != 'v'
+            && self.initial(col, row + time) != '^'
+    }
+}
+
+fn parse_valley(input: &str) -> Result<Valley, String> {
+    let lines: Vec<_> = input.lines().collect();
+    if lines.len() < 3 {
+        return Err("Valley is too small".to_string());
+    }
+
+    let tiles: Vec<Vec<char>> = lines[1..lines.len() - 1]
+        .iter()
+        .map(|line| {
+            let row: Vec<_> = line.chars().collect();
+            row[1..row.len() - 1].to_vec()
+        })
+        .collect();
+
+    Ok(Valley {
+        width: tiles[0].len() as isize,
+        height: tiles.len() as isize,
+        tiles,
+    })
+}
+
+/// returns the time of arrival at `to` when leaving `from` at `time`.
+///
+/// The blizzards repeat every [`Valley::period`] minutes, so the search state is the position and
+/// the time within that period. Each minute costs 1 and moves at most one step, so the manhattan
+/// distance to `to` never overestimates the remaining time.
+fn trip(valley: &Valley, from: Direction, to: Direction, time: usize) -> Option<usize> {
+    let period = valley.period();
+    let neighbors = |(position, phase): (Direction, usize)| {
+        let phase = (phase + 1) % period;
+
+        MOVES.iter().filter_map(move |&step| {
+            let next = position + step;
+            if valley.is_clear(next, phase) {
+                Some(((next, phase), 1))
+            } else {
+                None
+            }
+        })
+    };
+
+    astar(
+        (from, time % period),
+        neighbors,
+        |(position, _)| position == to,
+        |(position, _)| position.manhattan(&to) as u32,
+    )
+    .map(|(distance, _)| time + distance as usize)
+}
+
+/// returns the fewest minutes required to reach the goal
+pub fn one(file_path: &str) -> usize {
+    let valley = parse_valley(&read_file(file_path)).unwrap();
+
+    trip(&valley, valley.start(), valley.goal(), 0).expect("Goal is unreachable")
+}
+
+/// returns the fewest minutes required to reach the goal, go back to the start, then reach the
+/// goal again
+pub fn two(file_path: &str) -> usize {
+    let valley = parse_valley(&read_file(file_path)).unwrap();
+    let (start, goal) = (valley.start(), valley.goal());
+
+    Some(0)
+        .and_then(|time| trip(&valley, start, goal, time))
+        .and_then(|time| trip(&valley, goal, start, time))
+        .and_then(|time| trip(&valley, start, goal, time))
+        .expect("Goal is unreachable")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn blizzards() {
+        let msg = "should return true if the position is free of blizzards";
+        let valley = parse_valley(&read_file("input/24-t.txt")).unwrap();
+        // minute 1, top row of the interior: #.>3.<.#
+        let cases = [
+            (Direction(0, 0), true),
+            (Direction(1, 0), false),
+            (Direction(2, 0), false),
+            (Direction(3, 0), true),
+            (Direction(4, 0), false),
+            (Direction(5, 0), true),
+        ];
+        for (position, expected) in cases {
+            let actual = valley.is_clear(position, 1);
+            assert_eq!(actual, expected, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the fewest minutes required to reach the goal";
+        let expected = 18;
+        let actual = one("input/24-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn part_two() {
+        let msg = "should return the fewest minutes required to reach the goal, go back to the start, then reach the goal again";
+        let expected = 54;
+        let actual = two("input/24-t.txt");
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
@@ -0,0 +1,167 @@
+//! Solutions to 2022 day 25 problems
+//! --- Day 25: Full of Hot Air ---
+use std::fmt;
+use std::iter::Sum;
+use std::str::FromStr;
+
+use crate::read_file;
+
+/// Special Numeral-Analogue Fuel Units number: balanced base 5, using the digits `=`, `-`, `0`,
+/// `1` and `2` for -2 through 2
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Snafu(pub i64);
+
+impl From<i64> for Snafu {
+    fn from(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Snafu> for i64 {
+    fn from(value: Snafu) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.is_empty() {
+            return Err("Empty SNAFU number".to_string());
+        }
+
+        let out_of_range = || format!("SNAFU number out of range: '{}'", value);
+        // prefixes of an in range number may briefly fall outside i64, e.g. for i64::MIN
+        value
+            .chars()
+            .try_fold(0_i128, |acc, ch| {
+                let digit = match ch {
+                    '=' => -2,
+                    '-' => -1,
+                    '0' => 0,
+                    '1' => 1,
+                    '2' => 2,
+                    _ => return Err(format!("Invalid SNAFU digit: '{}'", ch)),
+                };
+
+                acc.checked_mul(5)
+                    .and_then(|acc| acc.checked_add(digit))
+                    .ok_or_else(out_of_range)
+            })
+            .and_then(|acc| i64::try_from(acc).map_err(|_| out_of_range()))
+            .map(Self)
+    }
+}
+
+impl fmt::Display for Snafu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0");
+        }
+
+        let mut digits = Vec::new();
+        let mut remain = self.0;
+        while remain != 0 {
+            let (ch, carry) = match remain.rem_euclid(5) {
+                0 => ('0', 0),
+                1 => ('1', 0),
+                2 => ('2', 0),
+                3 => ('=', 1),
+                _ => ('-', 1),
+            };
+            digits.push(ch);
+            // negative digits borrow from the next place. Dividing first keeps i64::MIN in range
+            remain = remain.div_euclid(5) + carry;
+        }
+
+        write!(f, "{}", digits.iter().rev().collect::<String>())
+    }
+}
+
+impl Sum for Snafu {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|snafu| snafu.0).sum())
+    }
+}
+
+/// returns the SNAFU sum of the fuel requirements
+pub fn one(file_path: &str) -> Snafu {
+    read_file(file_path)
+        .lines()
+        .map(FromStr::from_str)
+        .collect::<Result<Vec<Snafu>, _>>()
+        .unwrap()
+        .into_iter()
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLES: [(i64, &str); 15] = [
+        (1, "1"),
+        (2, "2"),
+        (3, "1="),
+        (4, "1-"),
+        (5, "10"),
+        (6, "11"),
+        (7, "12"),
+        (8, "2="),
+        (9, "2-"),
+        (10, "20"),
+        (15, "1=0"),
+        (20, "1-0"),
+        (2022, "1=11-2"),
+        (12345, "1-0---0"),
+        (314159265, "1121-1110-1=0"),
+    ];
+
+    #[test]
+    fn from_snafu() {
+        let msg = "should parse a SNAFU number";
+        for (expected, input) in EXAMPLES {
+            let actual = input.parse::<Snafu>().map(i64::from);
+            assert_eq!(actual, Ok(expected), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn to_snafu() {
+        let msg = "should format a number as SNAFU";
+        for (input, expected) in EXAMPLES {
+            let actual = Snafu::from(input).to_string();
+            assert_eq!(actual, expected, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let msg = "should return the original value after formatting and parsing";
+        for expected in -10_000..=10_000 {
+            let actual = Snafu(expected).to_string().parse();
+            assert_eq!(actual, Ok(Snafu(expected)), "{}", msg);
+        }
+        for expected in [i64::MAX, i64::MIN, i64::MIN + 1] {
+            let actual = Snafu(expected).to_string().parse();
+            assert_eq!(actual, Ok(Snafu(expected)), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let msg = "should return an error for invalid SNAFU numbers";
+        for input in ["", "3", "1a", "2222222222222222222222222222"] {
+            assert!(input.parse::<Snafu>().is_err(), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn part_one() {
+        let msg = "should return the SNAFU sum of the fuel requirements";
+        let expected = "2=-1=0";
+        let actual = one("input/25-t.txt").to_string();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
@@ -0,0 +1,355 @@
+//! Points, vectors and shapes on integer grids
+//!
+//! Unless noted otherwise, 2d grids use screen coordinates: `.0` is the column (x) and `.1` is the
+//! row (y), increasing downward.
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Grid position or offset
+#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq)]
+pub struct Direction(pub isize, pub isize);
+
+impl Add for Direction {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl Sub for Direction {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl Mul<isize> for Direction {
+    type Output = Self;
+
+    fn mul(self, scalar: isize) -> Self {
+        Self(self.0 * scalar, self.1 * scalar)
+    }
+}
+
+impl Neg for Direction {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0, -self.1)
+    }
+}
+
+/// No movement
+pub const ZERO: Direction = Direction(0, 0);
+/// One row up
+pub const NORTH: Direction = Direction(0, -1);
+/// One row down
+pub const SOUTH: Direction = Direction(0, 1);
+/// One column right
+pub const EAST: Direction = Direction(1, 0);
+/// One column left
+pub const WEST: Direction = Direction(-1, 0);
+/// Up and right
+pub const NORTH_EAST: Direction = Direction(1, -1);
+/// Up and left
+pub const NORTH_WEST: Direction = Direction(-1, -1);
+/// Down and right
+pub const SOUTH_EAST: Direction = Direction(1, 1);
+/// Down and left
+pub const SOUTH_WEST: Direction = Direction(-1, 1);
+
+/// Orthogonal steps, clockwise from north
+pub const CARDINALS: [Direction; 4] = [NORTH, EAST, SOUTH, WEST];
+
+/// Diagonal steps, clockwise from north east
+pub const DIAGONALS: [Direction; 4] = [NORTH_EAST, SOUTH_EAST, SOUTH_WEST, NORTH_WEST];
+
+/// Orthogonal and diagonal steps, clockwise from north
+pub const ADJACENT: [Direction; 8] = [
+    NORTH, NORTH_EAST, EAST, SOUTH_EAST, SOUTH, SOUTH_WEST, WEST, NORTH_WEST,
+];
+
+impl Direction {
+    /// Returns the vector rotated a quarter turn clockwise
+    pub fn rotate_cw(self) -> Self {
+        Self(-self.1, self.0)
+    }
+
+    /// Returns the vector rotated a quarter turn counterclockwise
+    pub fn rotate_ccw(self) -> Self {
+        Self(self.1, -self.0)
+    }
+
+    /// Returns the vector with each component clamped to -1..=1
+    pub fn signum(self) -> Self {
+        Self(self.0.signum(), self.1.signum())
+    }
+
+    /// Returns the manhattan distance between two points
+    pub fn manhattan(&self, other: &Direction) -> usize {
+        other.0.abs_diff(self.0) + other.1.abs_diff(self.1)
+    }
+
+    /// Returns the chebyshev (king move) distance between two points
+    pub fn chebyshev(&self, other: &Direction) -> usize {
+        other.0.abs_diff(self.0).max(other.1.abs_diff(self.1))
+    }
+}
+
+/// Returns every point on the line from `start` to `end`, inclusive
+pub fn line(start: Direction, end: Direction) -> impl Iterator<Item = Direction> {
+    // Bresenham's line algorithm
+    let dx = end.0.abs_diff(start.0) as isize;
+    let dy = -(end.1.abs_diff(start.1) as isize);
+    let step = (end - start).signum();
+    let mut error = dx + dy;
+    let mut current = Some(start);
+
+    std::iter::from_fn(move || {
+        let point = current?;
+        current = if point == end {
+            None
+        } else {
+            let mut next = point;
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                next.0 += step.0;
+            }
+            if doubled <= dx {
+                error += dx;
+                next.1 += step.1;
+            }
+            Some(next)
+        };
+
+        Some(point)
+    })
+}
+
+/// Smallest axis aligned rectangle containing a set of points
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BoundingBox {
+    /// Smallest x and y values
+    pub min: Direction,
+    /// Largest x and y values
+    pub max: Direction,
+}
+
+impl BoundingBox {
+    /// Returns the bounding box of `points`, or None if there are no points
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Direction>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        Some(points.fold(
+            Self {
+                min: first,
+                max: first,
+            },
+            Self::include,
+        ))
+    }
+
+    /// Returns the bounding box grown to contain `point`
+    pub fn include(self, point: Direction) -> Self {
+        Self {
+            min: Direction(self.min.0.min(point.0), self.min.1.min(point.1)),
+            max: Direction(self.max.0.max(point.0), self.max.1.max(point.1)),
+        }
+    }
+
+    /// Returns the bounding box grown by `margin` on every side
+    pub fn expand(self, margin: isize) -> Self {
+        Self {
+            min: self.min - Direction(margin, margin),
+            max: self.max + Direction(margin, margin),
+        }
+    }
+
+    /// Returns true if `point` is inside the bounding box, including its edges
+    pub fn contains(&self, point: Direction) -> bool {
+        (self.min.0..=self.max.0).contains(&point.0) && (self.min.1..=self.max.1).contains(&point.1)
+    }
+
+    /// Returns the number of columns covered
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+
+    /// Returns the number of rows covered
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    /// Returns the number of grid points covered
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+}
+
+/// 3d grid position
+#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq)]
+pub struct Point3(pub isize, pub isize, pub isize);
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0, self.1 + other.1, self.2 + other.2)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0, self.1 - other.1, self.2 - other.2)
+    }
+}
+
+impl Mul<isize> for Point3 {
+    type Output = Self;
+
+    fn mul(self, scalar: isize) -> Self {
+        Self(self.0 * scalar, self.1 * scalar, self.2 * scalar)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0, -self.1, -self.2)
+    }
+}
+
+impl Point3 {
+    /// unit steps along each axis
+    pub const FACES: [Point3; 6] = [
+        Point3(1, 0, 0),
+        Point3(-1, 0, 0),
+        Point3(0, 1, 0),
+        Point3(0, -1, 0),
+        Point3(0, 0, 1),
+        Point3(0, 0, -1),
+    ];
+
+    /// Returns the 6 positions sharing a face with this one
+    pub fn neighbors(self) -> impl Iterator<Item = Point3> {
+        Self::FACES.into_iter().map(move |step| self + step)
+    }
+
+    /// Returns the component-wise minimum of two points
+    pub fn min(self, other: Point3) -> Point3 {
+        Self(
+            self.0.min(other.0),
+            self.1.min(other.1),
+            self.2.min(other.2),
+        )
+    }
+
+    /// Returns the component-wise maximum of two points
+    pub fn max(self, other: Point3) -> Point3 {
+        Self(
+            self.0.max(other.0),
+            self.1.max(other.1),
+            self.2.max(other.2),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rotate() {
+        let msg = "should rotate a quarter turn";
+        assert_eq!(NORTH.rotate_cw(), EAST, "{}", msg);
+        assert_eq!(EAST.rotate_cw(), SOUTH, "{}", msg);
+        assert_eq!(NORTH.rotate_ccw(), WEST, "{}", msg);
+        assert_eq!(
+            Direction(2, 1).rotate_cw().rotate_ccw(),
+            Direction(2, 1),
+            "{}",
+            msg
+        );
+        for step in ADJACENT {
+            let actual = step.rotate_cw().rotate_cw().rotate_cw().rotate_cw();
+            assert_eq!(actual, step, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn distance() {
+        let msg = "should return the distance between two points";
+        let a = Direction(1, -2);
+        let b = Direction(-3, 5);
+        assert_eq!(a.manhattan(&b), 11, "{}", msg);
+        assert_eq!(a.chebyshev(&b), 7, "{}", msg);
+        assert_eq!(a.manhattan(&a), 0, "{}", msg);
+    }
+
+    #[test]
+    fn scale() {
+        let msg = "should multiply each component";
+        assert_eq!(SOUTH_WEST * 3, Direction(-3, 3), "{}", msg);
+        assert_eq!(-SOUTH_WEST, NORTH_EAST, "{}", msg);
+    }
+
+    #[test]
+    fn raster_line() {
+        let msg = "should return every point on the line";
+        let actual: Vec<_> = line(Direction(498, 4), Direction(498, 6)).collect();
+        let expected = vec![Direction(498, 4), Direction(498, 5), Direction(498, 6)];
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual: Vec<_> = line(Direction(498, 6), Direction(496, 6)).collect();
+        let expected = vec![Direction(498, 6), Direction(497, 6), Direction(496, 6)];
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual: Vec<_> = line(Direction(0, 0), Direction(-2, 2)).collect();
+        let expected = vec![Direction(0, 0), Direction(-1, 1), Direction(-2, 2)];
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual: Vec<_> = line(Direction(0, 0), Direction(5, 2)).collect();
+        let expected = vec![
+            Direction(0, 0),
+            Direction(1, 0),
+            Direction(2, 1),
+            Direction(3, 1),
+            Direction(4, 2),
+            Direction(5, 2),
+        ];
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual: Vec<_> = line(Direction(3, 3), Direction(3, 3)).collect();
+        assert_eq!(actual, vec![Direction(3, 3)], "{}", msg);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let msg = "should return the smallest rectangle containing every point";
+        let points = [Direction(2, -1), Direction(-1, 3), Direction(0, 0)];
+        let actual = BoundingBox::from_points(points).unwrap();
+        let expected = BoundingBox {
+            min: Direction(-1, -1),
+            max: Direction(2, 3),
+        };
+        assert_eq!(actual, expected, "{}", msg);
+        assert_eq!(actual.width(), 4, "{}", msg);
+        assert_eq!(actual.height(), 5, "{}", msg);
+        assert_eq!(actual.area(), 20, "{}", msg);
+        assert!(actual.contains(Direction(2, 3)), "{}", msg);
+        assert!(!actual.contains(Direction(3, 3)), "{}", msg);
+        assert_eq!(actual.expand(1).area(), 42, "{}", msg);
+
+        let msg = "should return None for an empty set of points";
+        assert_eq!(BoundingBox::from_points([]), None, "{}", msg);
+    }
+}
@@ -0,0 +1,334 @@
+//! Closed integer intervals, and sets of them
+use std::cmp::{max, min};
+
+/// Closed range of integers, `start..=end`
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Interval {
+    start: isize,
+    end: isize,
+}
+
+impl Interval {
+    /// Create the interval `start..=end`
+    ///
+    /// Panic! if `start` is greater than `end`
+    pub fn new(start: isize, end: isize) -> Self {
+        assert!(
+            start <= end,
+            "Interval start {} is after end {}",
+            start,
+            end
+        );
+        Self { start, end }
+    }
+
+    /// first value in the interval
+    pub fn start(&self) -> isize {
+        self.start
+    }
+
+    /// last value in the interval
+    pub fn end(&self) -> isize {
+        self.end
+    }
+
+    /// number of values in the interval
+    pub fn length(&self) -> usize {
+        self.end.abs_diff(self.start) + 1
+    }
+
+    /// returns true if `value` lies within the interval
+    pub fn contains(&self, value: isize) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// returns true if `other` lies entirely within this interval
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// returns true if the intervals share at least one value
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// returns the values common to both intervals, if any
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        if self.overlaps(other) {
+            Some(Interval::new(
+                max(self.start, other.start),
+                min(self.end, other.end),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Set of integers, stored as sorted, disjoint, non-adjacent [`Interval`]s
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// returns true if the set has no values
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// iterate over the merged intervals in ascending order
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval> {
+        self.intervals.iter()
+    }
+
+    /// total number of values in the set
+    pub fn length(&self) -> usize {
+        self.intervals.iter().map(Interval::length).sum()
+    }
+
+    /// returns true if `value` is in the set
+    pub fn contains(&self, value: isize) -> bool {
+        // the last interval starting at or before the value is the only one that may hold it
+        let idx = self
+            .intervals
+            .partition_point(|interval| interval.start <= value);
+        idx > 0 && self.intervals[idx - 1].contains(value)
+    }
+
+    /// add every value in `interval`, merging with any overlapping or adjacent intervals
+    pub fn insert(&mut self, interval: Interval) {
+        let mut merged = interval;
+        let mut result = Vec::with_capacity(self.intervals.len() + 1);
+        let mut is_placed = false;
+
+        for &current in &self.intervals {
+            if current.end < merged.start.saturating_sub(1) {
+                // entirely before
+                result.push(current);
+            } else if merged.end < current.start.saturating_sub(1) {
+                // entirely after
+                if !is_placed {
+                    result.push(merged);
+                    is_placed = true;
+                }
+                result.push(current);
+            } else {
+                merged = Interval::new(
+                    min(merged.start, current.start),
+                    max(merged.end, current.end),
+                );
+            }
+        }
+        if !is_placed {
+            result.push(merged);
+        }
+
+        self.intervals = result;
+    }
+
+    /// remove every value in `interval`
+    pub fn remove(&mut self, interval: Interval) {
+        let mut result = Vec::with_capacity(self.intervals.len() + 1);
+
+        for current in self.intervals.drain(..) {
+            if !current.overlaps(&interval) {
+                result.push(current);
+                continue;
+            }
+
+            if current.start < interval.start {
+                result.push(Interval::new(current.start, interval.start - 1));
+            }
+            if current.end > interval.end {
+                result.push(Interval::new(interval.end + 1, current.end));
+            }
+        }
+
+        self.intervals = result;
+    }
+
+    /// returns the values in either set
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = self.clone();
+        for &interval in other.iter() {
+            result.insert(interval);
+        }
+
+        result
+    }
+
+    /// returns the values in both sets
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter()
+            .flat_map(|a| other.iter().filter_map(|b| a.intersection(b)))
+            .collect()
+    }
+
+    /// returns the values in this set that are not in `other`
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut result = self.clone();
+        for &interval in other.iter() {
+            result.remove(interval);
+        }
+
+        result
+    }
+
+    /// iterate over the missing values between the first and last intervals of the set
+    pub fn gaps(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|pair| Interval::new(pair[0].end + 1, pair[1].start - 1))
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut result = IntervalSet::new();
+        for interval in iter {
+            result.insert(interval);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    /// values the generated intervals are drawn from
+    const DOMAIN: isize = 40;
+
+    /// deterministic pseudo random source for the property tests
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: isize) -> isize {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) % bound as u64) as isize
+        }
+
+        fn interval(&mut self) -> Interval {
+            let start = self.next(DOMAIN) - DOMAIN / 2;
+            Interval::new(start, start + self.next(8))
+        }
+
+        fn set(&mut self) -> IntervalSet {
+            let count = self.next(6);
+            (0..count).map(|_| self.interval()).collect()
+        }
+    }
+
+    /// the values held by `set`, enumerated one by one
+    fn values(set: &IntervalSet) -> BTreeSet<isize> {
+        set.iter()
+            .flat_map(|interval| interval.start()..=interval.end())
+            .collect()
+    }
+
+    /// returns true if the set's intervals are sorted, disjoint and non-adjacent
+    fn is_normalized(set: &IntervalSet) -> bool {
+        set.intervals
+            .windows(2)
+            .all(|pair| pair[0].end + 1 < pair[1].start)
+    }
+
+    #[test]
+    fn interval() {
+        let msg = "should compare intervals";
+        let a = Interval::new(2, 8);
+        let b = Interval::new(3, 7);
+        assert!(a.contains_interval(&b), "{}", msg);
+        assert!(!b.contains_interval(&a), "{}", msg);
+        assert!(a.overlaps(&Interval::new(8, 9)), "{}", msg);
+        assert!(!a.overlaps(&Interval::new(9, 9)), "{}", msg);
+        assert_eq!(
+            a.intersection(&Interval::new(6, 12)),
+            Some(Interval::new(6, 8)),
+            "{}",
+            msg
+        );
+        assert_eq!(a.intersection(&Interval::new(-4, 1)), None, "{}", msg);
+        assert_eq!(a.length(), 7, "{}", msg);
+    }
+
+    #[test]
+    fn merge() {
+        let msg = "should merge overlapping and adjacent intervals";
+        let actual: IntervalSet = [
+            Interval::new(10, 12),
+            Interval::new(0, 2),
+            Interval::new(3, 4),
+            Interval::new(7, 8),
+            Interval::new(8, 11),
+        ]
+        .into_iter()
+        .collect();
+        let expected = vec![Interval::new(0, 4), Interval::new(7, 12)];
+        assert_eq!(
+            actual.iter().copied().collect::<Vec<_>>(),
+            expected,
+            "{}",
+            msg
+        );
+        assert_eq!(actual.length(), 11, "{}", msg);
+        assert_eq!(
+            actual.gaps().collect::<Vec<_>>(),
+            vec![Interval::new(5, 6)],
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn set_properties() {
+        let msg = "should match the equivalent operations on the enumerated values";
+        let mut rng = Lcg(2022);
+
+        for _ in 0..500 {
+            let a = rng.set();
+            let b = rng.set();
+            let (a_values, b_values) = (values(&a), values(&b));
+
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+            for set in [&a, &b, &union, &intersection, &difference] {
+                assert!(is_normalized(set), "{}", msg);
+            }
+
+            let expected: BTreeSet<_> = a_values.union(&b_values).copied().collect();
+            assert_eq!(values(&union), expected, "{}", msg);
+            let expected: BTreeSet<_> = a_values.intersection(&b_values).copied().collect();
+            assert_eq!(values(&intersection), expected, "{}", msg);
+            let expected: BTreeSet<_> = a_values.difference(&b_values).copied().collect();
+            assert_eq!(values(&difference), expected, "{}", msg);
+
+            assert_eq!(a.length(), a_values.len(), "{}", msg);
+            for value in -DOMAIN..DOMAIN {
+                assert_eq!(a.contains(value), a_values.contains(&value), "{}", msg);
+            }
+
+            let gaps: BTreeSet<_> = a.gaps().flat_map(|gap| gap.start()..=gap.end()).collect();
+            let expected: BTreeSet<_> = match (a_values.first(), a_values.last()) {
+                (Some(&first), Some(&last)) => (first..=last)
+                    .filter(|value| !a_values.contains(value))
+                    .collect(),
+                _ => BTreeSet::new(),
+            };
+            assert_eq!(gaps, expected, "{}", msg);
+        }
+    }
+}
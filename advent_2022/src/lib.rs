@@ -1,9 +1,16 @@
 #![deny(missing_debug_implementations)]
 #![deny(missing_docs)]
 //! Advent of Code 2022 Solutions
-use std::{fmt, fs, ops::RangeInclusive, path::Path};
+use std::{
+    fmt, fs,
+    ops::{Index, IndexMut, RangeInclusive},
+    path::Path,
+    str::FromStr,
+};
 
-use parser::three::lib::{any_of, choice, keep_first, p_char, p_int, spaces};
+use parser::three::lib::{any_of, choice, keep_first, keep_second, p_char, p_int, spaces};
+
+use geometry::{Direction, ADJACENT, CARDINALS, ZERO};
 
 pub mod day_01;
 pub mod day_02;
@@ -20,6 +27,18 @@ pub mod day_12;
 pub mod day_14;
 pub mod day_15;
 pub mod day_16;
+pub mod day_17;
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
+pub mod geometry;
+pub mod interval;
+pub mod search;
 
 /// read the specified file at `file_path` into a `String`
 ///
@@ -31,17 +50,222 @@ pub fn read_file(file_path: &str) -> String {
     fs::read_to_string(path).unwrap_or_else(|why| panic!("couldnt open {}: {}", display, why))
 }
 
-/// deserializes a 2d vec of [`T`] from the specified file path
-pub fn load_terrain<T>(file_path: &str) -> Vec<Vec<T>>
+/// deserializes a 2d vec of [`T`] from the supplied text
+pub fn parse_terrain<T>(input: &str) -> Vec<Vec<T>>
 where
     T: From<char>,
 {
-    read_file(file_path)
+    input
         .lines()
         .map(|line| line.chars().map(From::from).collect::<Vec<_>>())
         .collect()
 }
 
+/// deserializes a 2d vec of [`T`] from the specified file path
+pub fn load_terrain<T>(file_path: &str) -> Vec<Vec<T>>
+where
+    T: From<char>,
+{
+    parse_terrain(&read_file(file_path))
+}
+
+/// Dense 2d grid of [`T`], indexed by a [`Direction`] holding the column and row
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a `width` by `height` grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Create a grid from a list of equal length rows
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(idx) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "Row {} has length {}, expected {}",
+                idx,
+                rows[idx].len(),
+                width
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Returns the number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index_of(&self, position: Direction) -> Option<usize> {
+        let Direction(col, row) = position;
+        if col < 0 || row < 0 || col as usize >= self.width || row as usize >= self.height {
+            return None;
+        }
+
+        Some(row as usize * self.width + col as usize)
+    }
+
+    /// Returns true if `position` is within the grid bounds
+    pub fn contains(&self, position: Direction) -> bool {
+        self.index_of(position).is_some()
+    }
+
+    /// Returns a reference to the cell at `position`, or None if out of bounds
+    pub fn get(&self, position: Direction) -> Option<&T> {
+        self.index_of(position).map(|idx| &self.cells[idx])
+    }
+
+    /// Returns a mutable reference to the cell at `position`, or None if out of bounds
+    pub fn get_mut(&mut self, position: Direction) -> Option<&mut T> {
+        self.index_of(position).map(move |idx| &mut self.cells[idx])
+    }
+
+    /// Returns every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Direction> {
+        let width = self.width;
+        (0..self.width * self.height)
+            .map(move |idx| Direction((idx % width) as isize, (idx / width) as isize))
+    }
+
+    /// Returns every position and cell in the grid, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Direction, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the cells in each row, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, but an empty grid has no cells to chunk anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns the cells in row `row`, or None if out of bounds
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        if row < self.height {
+            Some(&self.cells[row * self.width..(row + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cells in column `col` from top to bottom. Empty if out of bounds.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let height = if col < self.width { self.height } else { 0 };
+        self.cells
+            .iter()
+            .skip(col)
+            .step_by(self.width.max(1))
+            .take(height)
+    }
+
+    /// Returns the positions and cells from `start` (exclusive) repeatedly moving by `step`, until
+    /// leaving the grid
+    ///
+    /// Panics if `step` is zero
+    pub fn ray(&self, start: Direction, step: Direction) -> impl Iterator<Item = (Direction, &T)> {
+        assert!(step != ZERO, "Ray step must be non-zero");
+        std::iter::successors(Some(start + step), move |&position| Some(position + step))
+            .map_while(move |position| self.get(position).map(|cell| (position, cell)))
+    }
+
+    /// Returns the in bounds orthogonal neighbors of `position`
+    pub fn neighbors(&self, position: Direction) -> impl Iterator<Item = (Direction, &T)> {
+        self.offsets(position, &CARDINALS)
+    }
+
+    /// Returns the in bounds orthogonal and diagonal neighbors of `position`
+    pub fn neighbors8(&self, position: Direction) -> impl Iterator<Item = (Direction, &T)> {
+        self.offsets(position, &ADJACENT)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        position: Direction,
+        steps: &'a [Direction],
+    ) -> impl Iterator<Item = (Direction, &'a T)> {
+        steps.iter().filter_map(move |&step| {
+            let next = position + step;
+            self.get(next).map(|cell| (next, cell))
+        })
+    }
+
+    /// Returns a grid of the same size with `f` applied to every cell
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Direction> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Direction) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("Position {:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Direction> for Grid<T> {
+    fn index_mut(&mut self, position: Direction) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("Position {:?} is outside the grid", position))
+    }
+}
+
+// parse a grid with one cell per character
+impl<T: From<char>> FromStr for Grid<T> {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::from_rows(parse_terrain(input))
+    }
+}
+
+// render a grid with each row on its own line
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// AoC problem solver function pointer
 pub type Solver<T> = fn(&str) -> T;
 
@@ -104,6 +328,8 @@ pub enum Command {
     Day(usize),
     /// Run solutions for a range of days
     Range(RangeInclusive<usize>),
+    /// Print the day 5 stacks after every given number of instructions
+    Stacks(usize),
 }
 
 // parse a command from user input
@@ -119,7 +345,10 @@ impl std::str::FromStr for Command {
         let quit = any_of(['q', 'Q']).and_then(spaces()).map(|_| Command::Quit);
         let all = any_of(['a', 'A']).and_then(spaces()).map(|_| Command::All);
 
-        let p_command = choice([range, day, all, quit]);
+        let stacks = keep_second(any_of(['s', 'S']).and_then(spaces()), p_int(10))
+            .map(|every| Command::Stacks(every as usize));
+
+        let p_command = choice([range, day, all, quit, stacks]);
 
         match p_command.parse(input) {
             Ok((_, command)) => Ok(command),
@@ -132,6 +361,60 @@ impl std::str::FromStr for Command {
 mod test {
     use super::*;
 
+    #[test]
+    fn grid_parse() {
+        let msg = "should parse and render a grid";
+        let input = "ab\ncd\nef";
+        let grid: Grid<char> = input.parse().unwrap();
+        assert_eq!(grid.width(), 2, "{}", msg);
+        assert_eq!(grid.height(), 3, "{}", msg);
+        assert_eq!(grid[Direction(1, 2)], 'f', "{}", msg);
+        assert_eq!(grid.to_string(), input, "{}", msg);
+
+        let msg = "should return an error for uneven rows";
+        assert!("ab\nc".parse::<Grid<char>>().is_err(), "{}", msg);
+    }
+
+    #[test]
+    fn grid_get() {
+        let msg = "should return None for positions outside the grid";
+        let grid: Grid<char> = "ab\ncd".parse().unwrap();
+        assert_eq!(grid.get(Direction(1, 0)), Some(&'b'), "{}", msg);
+        assert_eq!(grid.get(Direction(-1, 0)), None, "{}", msg);
+        assert_eq!(grid.get(Direction(2, 0)), None, "{}", msg);
+        assert_eq!(grid.get(Direction(0, 2)), None, "{}", msg);
+    }
+
+    #[test]
+    fn grid_lines() {
+        let msg = "should iterate the cells in a line of the grid";
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]), "{}", msg);
+        assert_eq!(grid.row(3), None, "{}", msg);
+        assert_eq!(grid.column(2).collect::<String>(), "cfi", "{}", msg);
+        assert_eq!(grid.column(3).count(), 0, "{}", msg);
+
+        let actual: String = grid
+            .ray(Direction(0, 2), Direction(1, -1))
+            .map(|(_, &cell)| cell)
+            .collect();
+        assert_eq!(actual, "ec", "{}", msg);
+    }
+
+    #[test]
+    fn grid_neighbors() {
+        let msg = "should return the in bounds neighbors of a position";
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+        let actual: String = grid.neighbors(Direction(0, 0)).map(|(_, &c)| c).collect();
+        assert_eq!(actual, "bd", "{}", msg);
+        let actual: String = grid.neighbors(Direction(1, 1)).map(|(_, &c)| c).collect();
+        assert_eq!(actual, "bfhd", "{}", msg);
+        let actual: String = grid.neighbors8(Direction(1, 1)).map(|(_, &c)| c).collect();
+        assert_eq!(actual, "bcfihgda", "{}", msg);
+        let actual: String = grid.neighbors8(Direction(2, 2)).map(|(_, &c)| c).collect();
+        assert_eq!(actual, "fhe", "{}", msg);
+    }
+
     #[test]
     fn command_parser() {
         let msg = "should parse user input into a command";
@@ -159,6 +442,10 @@ mod test {
         let actual: Command = "10".parse().unwrap();
         assert_eq!(actual, expected, "{}", msg);
 
+        let expected = Command::Stacks(100);
+        let actual: Command = "s 100".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
         let actual = "foo".parse::<Command>().is_err();
         assert!(actual, "{}", msg);
     }
@@ -99,6 +99,47 @@ static SOLUTIONS: &[Option<Solution<String>>] = &[
     )),
     Some(to_solution!("15-1", (day_15::one, "beacon free zones"))),
     Some(to_solution!("16-1", (day_16::one, "volcano graph walk"))),
+    Some(to_solution!(
+        "17-1",
+        (day_17::one, "tower height"),
+        (day_17::two, "very tall tower height")
+    )),
+    Some(to_solution!(
+        "18-1",
+        (day_18::one, "droplet surface area"),
+        (day_18::two, "exterior surface area")
+    )),
+    Some(to_solution!(
+        "19-1",
+        (day_19::one, "blueprint quality levels"),
+        (day_19::two, "geodes from first three blueprints")
+    )),
+    Some(to_solution!(
+        "20-1",
+        (day_20::one, "grove coordinates"),
+        (day_20::two, "decrypted grove coordinates")
+    )),
+    Some(to_solution!(
+        "21-1",
+        (day_21::one, "root monkey number"),
+        (day_21::two, "number to yell")
+    )),
+    Some(to_solution!(
+        "22-1",
+        (day_22::one, "map password"),
+        (day_22::two, "cube password")
+    )),
+    Some(to_solution!(
+        "23-1",
+        (day_23::one, "empty ground tiles"),
+        (day_23::two, "first still round")
+    )),
+    Some(to_solution!(
+        "24-1",
+        (day_24::one, "blizzard crossing"),
+        (day_24::two, "return trip for snacks")
+    )),
+    Some(to_solution!("25-1", (day_25::one, "SNAFU fuel sum"))),
 ];
 
 /// run a single day
@@ -119,10 +160,13 @@ fn run_day(day: usize) -> Result<(), String> {
                 let (result, dur) = run(solver);
                 println!("\tPart 1 - {}: {} ({:?})", text, result, dur);
             });
-            solution.two.map(|(text, solver)| {
-                let (result, dur) = run(solver);
-                println!("\tPart 2 - {}: {} ({:?})", text, result, dur);
-            });
+            match solution.two {
+                Some((text, solver)) => {
+                    let (result, dur) = run(solver);
+                    println!("\tPart 2 - {}: {} ({:?})", text, result, dur);
+                }
+                None => println!("\tPart 2 - not available"),
+            }
         })
         .ok_or_else(|| format!("Day {:02} solution not found.", day))
 }
@@ -138,6 +182,19 @@ fn run_range(range: RangeInclusive<usize>) {
     println!();
 }
 
+/// print the day 5 stacks after every `every` instructions, for each crane
+fn print_stacks(every: usize) {
+    let input_path = format!("{}/input/05-1.txt", get_root_dir().display());
+    for crane in [day_05::Crane::CrateMover9000, day_05::Crane::CrateMover9001] {
+        println!("{:?}:", crane);
+        match day_05::trace(&input_path, crane, every) {
+            Ok(trace) => println!("{}", trace),
+            Err(msg) => println!("{}", msg),
+        }
+        println!();
+    }
+}
+
 /// clear the terminal
 fn clear_screen() {
     const ESC: char = 0x1B as char;
@@ -154,6 +211,7 @@ fn main() {
         println!("  a      for all days");
         println!("  #      or enter a day number (eg 17)");
         println!("  # - #  or enter a day range separated by a dash (eg 2-10)");
+        println!("  s #    to print the day 5 stacks every # instructions (eg s 50)");
         println!("  q      to quit");
         print!("-> ");
         io::stdout().flush().expect("Failed to flush stdout");
@@ -181,6 +239,10 @@ fn main() {
                 println!("Running day {}", day);
                 run_range(day..=day);
             }
+            Ok(Command::Stacks(every)) => {
+                println!("Printing day 5 stacks every {} instructions", every);
+                print_stacks(every);
+            }
             _ => {
                 println!("Unrecognized command: '{}'", input.trim());
                 println!("  len {}", input.len());
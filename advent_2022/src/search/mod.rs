@@ -0,0 +1,269 @@
+//! Graph searches over implicit graphs
+//!
+//! Graphs are described by a neighbor function returning the nodes reachable from a given node,
+//! paired with the cost of the edge for the weighted searches.
+use std::cmp::Ordering;
+use std::collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Result of a single source search: the distance to every reachable node, and the node each one
+/// was reached from
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Paths<T>
+where
+    T: Eq + Hash,
+{
+    /// cost of the shortest path from the start to each reachable node
+    pub distances: HashMap<T, u32>,
+    /// previous node on the shortest path to each reachable node, except the start
+    pub predecessors: HashMap<T, T>,
+}
+
+impl<T> Paths<T>
+where
+    T: Copy + Eq + Hash,
+{
+    fn new(start: T) -> Self {
+        Self {
+            distances: HashMap::from([(start, 0)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// returns the cost of the shortest path to `node`, if it is reachable
+    pub fn distance(&self, node: T) -> Option<u32> {
+        self.distances.get(&node).copied()
+    }
+
+    /// returns the nodes on the shortest path from the start to `node`, inclusive
+    pub fn path(&self, node: T) -> Option<Vec<T>> {
+        if !self.distances.contains_key(&node) {
+            return None;
+        }
+
+        let mut result = vec![node];
+        let mut current = node;
+        while let Some(&previous) = self.predecessors.get(&current) {
+            result.push(previous);
+            current = previous;
+        }
+        result.reverse();
+
+        Some(result)
+    }
+}
+
+/// breadth first search from `start`, treating every edge as cost 1
+pub fn bfs<T, F, I>(start: T, neighbors: F) -> Paths<T>
+where
+    T: Copy + Eq + Hash,
+    F: Fn(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut result = Paths::new(start);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = result.distances[&current] + 1;
+        for neighbor in neighbors(current) {
+            if let Entry::Vacant(entry) = result.distances.entry(neighbor) {
+                entry.insert(distance);
+                result.predecessors.insert(neighbor, current);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    result
+}
+
+/// priority queue entry, ordered so the lowest `priority` is popped from a [`BinaryHeap`] first
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct State<T> {
+    priority: u32,
+    cost: u32,
+    node: T,
+}
+
+impl<T: Eq> Ord for State<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<T: Eq> PartialOrd for State<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// best first search shared by [`dijkstra`] and [`astar`]. Stops early once a node satisfying
+/// `is_goal` is settled, returning it alongside the paths found so far.
+fn best_first<T, F, I, G, H>(
+    start: T,
+    neighbors: F,
+    is_goal: G,
+    heuristic: H,
+) -> (Option<T>, Paths<T>)
+where
+    T: Copy + Eq + Hash,
+    F: Fn(T) -> I,
+    I: IntoIterator<Item = (T, u32)>,
+    G: Fn(T) -> bool,
+    H: Fn(T) -> u32,
+{
+    let mut result = Paths::new(start);
+    let mut heap = BinaryHeap::from([State {
+        priority: heuristic(start),
+        cost: 0,
+        node: start,
+    }]);
+
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        if cost > result.distances[&node] {
+            // stale entry, a cheaper path was already found
+            continue;
+        }
+        if is_goal(node) {
+            return (Some(node), result);
+        }
+
+        for (neighbor, edge) in neighbors(node) {
+            let next = cost + edge;
+            if next < *result.distances.get(&neighbor).unwrap_or(&u32::MAX) {
+                result.distances.insert(neighbor, next);
+                result.predecessors.insert(neighbor, node);
+                heap.push(State {
+                    priority: next + heuristic(neighbor),
+                    cost: next,
+                    node: neighbor,
+                });
+            }
+        }
+    }
+
+    (None, result)
+}
+
+/// Dijkstra's shortest paths from `start`, where `neighbors` returns each adjacent node and the
+/// cost of the edge to it
+pub fn dijkstra<T, F, I>(start: T, neighbors: F) -> Paths<T>
+where
+    T: Copy + Eq + Hash,
+    F: Fn(T) -> I,
+    I: IntoIterator<Item = (T, u32)>,
+{
+    best_first(start, neighbors, |_| false, |_| 0).1
+}
+
+/// A* search from `start` to the nearest node satisfying `is_goal`, returning its cost and the
+/// path to it.
+///
+/// `heuristic` must never overestimate the remaining cost for the result to be the shortest path.
+pub fn astar<T, F, I, G, H>(
+    start: T,
+    neighbors: F,
+    is_goal: G,
+    heuristic: H,
+) -> Option<(u32, Vec<T>)>
+where
+    T: Copy + Eq + Hash,
+    F: Fn(T) -> I,
+    I: IntoIterator<Item = (T, u32)>,
+    G: Fn(T) -> bool,
+    H: Fn(T) -> u32,
+{
+    let (goal, paths) = best_first(start, neighbors, is_goal, heuristic);
+    let goal = goal?;
+
+    Some((paths.distances[&goal], paths.path(goal)?))
+}
+
+/// returns the shortest path cost between every pair of `nodes`, keyed by source then
+/// destination. Destinations include any other reachable node, not just those in `nodes`.
+pub fn all_pairs<T, N, F, I>(nodes: N, neighbors: F) -> HashMap<T, HashMap<T, u32>>
+where
+    T: Copy + Eq + Hash,
+    N: IntoIterator<Item = T>,
+    F: Fn(T) -> I,
+    I: IntoIterator<Item = (T, u32)>,
+{
+    nodes
+        .into_iter()
+        .map(|node| (node, dijkstra(node, &neighbors).distances))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// a -1- b -1- c -1- d, with a costly shortcut a -5- d
+    fn weighted(node: char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('d', 5)],
+            'b' => vec![('a', 1), ('c', 1)],
+            'c' => vec![('b', 1), ('d', 1)],
+            'd' => vec![('c', 1), ('a', 5)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn breadth_first() {
+        let msg = "should return the fewest edges to each node";
+        let paths = bfs('a', |node| weighted(node).into_iter().map(|(next, _)| next));
+        assert_eq!(paths.distance('c'), Some(2), "{}", msg);
+        assert_eq!(paths.distance('d'), Some(1), "{}", msg);
+        assert_eq!(paths.distance('z'), None, "{}", msg);
+        assert_eq!(paths.path('d'), Some(vec!['a', 'd']), "{}", msg);
+    }
+
+    #[test]
+    fn weighted_paths() {
+        let msg = "should return the cheapest path to each node";
+        let paths = dijkstra('a', weighted);
+        assert_eq!(paths.distance('d'), Some(3), "{}", msg);
+        assert_eq!(paths.path('d'), Some(vec!['a', 'b', 'c', 'd']), "{}", msg);
+        assert_eq!(paths.path('a'), Some(vec!['a']), "{}", msg);
+    }
+
+    #[test]
+    fn a_star() {
+        let msg = "should return the cheapest path to the goal";
+        let target = (4_i32, 3_i32);
+        let neighbors = |(x, y): (i32, i32)| {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .into_iter()
+                .map(move |(dx, dy)| (x + dx, y + dy))
+                // wall along x = 2, open at the bottom
+                .filter(|&(x, y)| (0..=5).contains(&x) && (0..=5).contains(&y))
+                .filter(|&(x, y)| x != 2 || y == 5)
+                .map(|node| (node, 1))
+        };
+        let manhattan = |(x, y): (i32, i32)| x.abs_diff(target.0) + y.abs_diff(target.1);
+
+        let (cost, path) = astar((0, 0), neighbors, |node| node == target, manhattan).unwrap();
+        assert_eq!(cost, 11, "{}", msg);
+        assert_eq!(path.len(), 12, "{}", msg);
+        assert_eq!(path.first(), Some(&(0, 0)), "{}", msg);
+        assert_eq!(path.last(), Some(&target), "{}", msg);
+
+        let expected = dijkstra((0, 0), neighbors).distance(target);
+        assert_eq!(Some(cost), expected, "{}", msg);
+
+        let msg = "should return None if the goal is unreachable";
+        let actual = astar((0, 0), neighbors, |node| node == (9, 9), |_| 0);
+        assert_eq!(actual, None, "{}", msg);
+    }
+
+    #[test]
+    fn pairs() {
+        let msg = "should return the cheapest cost between each pair of nodes";
+        let actual = all_pairs("abcd".chars(), weighted);
+        assert_eq!(actual.len(), 4, "{}", msg);
+        assert_eq!(actual[&'a'][&'d'], 3, "{}", msg);
+        assert_eq!(actual[&'d'][&'b'], 2, "{}", msg);
+        assert_eq!(actual[&'c'][&'c'], 0, "{}", msg);
+    }
+}